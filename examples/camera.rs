@@ -0,0 +1,124 @@
+use bella::prelude::*;
+use kurbo::{Affine, Rect, Vec2};
+use peniko::{Color, Fill};
+
+#[derive(Component)]
+struct Player;
+
+fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
+    instance.new_scene_with_layer("background", -1);
+    instance.set_scene_persistent("background", true);
+    instance.new_scene("world");
+    instance.new_scene("hud");
+    instance.set_screen_space("hud", true);
+
+    let player = commands
+        .spawn((Player, Transform::new(Affine::translate((0.0, 0.0)))))
+        .id();
+
+    commands.spawn(
+        Camera2d::default()
+            .with_follow(player)
+            .with_bounds(Rect::new(-1000.0, -1000.0, 1000.0, 1000.0)),
+    );
+}
+
+fn draw(
+    player: Query<&Transform, With<Player>>,
+    camera: Query<&Camera2d>,
+    input: Res<Input>,
+    mut instance: ResMut<Instance>,
+) {
+    // A grid, so the movement of the camera can be seen.
+    // It never changes, so it's only encoded once.
+    if instance.needs_redraw("background") {
+        let background = instance.get_scene("background").unwrap();
+
+        for x in -10..=10 {
+            for y in -10..=10 {
+                background.fill_circle(
+                    Fill::NonZero,
+                    Affine::translate((x as f64 * 100.0, y as f64 * 100.0)),
+                    Color::WHITE,
+                    4.0,
+                );
+            }
+        }
+    }
+
+    let scene = instance.get_scene("world").unwrap();
+
+    for t in &player {
+        scene.fill_circle(Fill::NonZero, t.affine, Color::RED, 25.0);
+    }
+
+    // The mouse cursor, converted from the window to the world.
+    let camera = camera.single();
+    let cursor = camera.screen_to_world(*input.mouse_position());
+
+    scene.fill_circle(Fill::NonZero, Affine::translate(cursor), Color::YELLOW, 8.0);
+
+    let hud = instance.get_scene("hud").unwrap();
+
+    hud.fill_rounded_rect(
+        Fill::NonZero,
+        Affine::translate((110.0, 40.0)),
+        Color::rgba(1.0, 1.0, 1.0, 0.5),
+        Vec2::new(200.0, 60.0),
+        10.0,
+    );
+}
+
+fn update(
+    time: Res<Time>,
+    input: Res<Input>,
+    mut player: Query<&mut Transform, With<Player>>,
+    mut camera: Query<&mut Camera2d>,
+) {
+    let speed = 300.0 * time.delta_seconds();
+
+    for mut t in &mut player {
+        if input.is_key_pressed(KeyCode::KeyW) {
+            t.affine = t.affine.then_translate(Vec2::new(0.0, -speed));
+        }
+
+        if input.is_key_pressed(KeyCode::KeyS) {
+            t.affine = t.affine.then_translate(Vec2::new(0.0, speed));
+        }
+
+        if input.is_key_pressed(KeyCode::KeyA) {
+            t.affine = t.affine.then_translate(Vec2::new(-speed, 0.0));
+        }
+
+        if input.is_key_pressed(KeyCode::KeyD) {
+            t.affine = t.affine.then_translate(Vec2::new(speed, 0.0));
+        }
+    }
+
+    let mut camera = camera.single_mut();
+
+    if input.is_key_pressed(KeyCode::KeyQ) {
+        camera.rotation -= time.delta_seconds();
+    }
+
+    if input.is_key_pressed(KeyCode::KeyE) {
+        camera.rotation += time.delta_seconds();
+    }
+
+    if input.is_key_pressed(KeyCode::KeyZ) {
+        camera.zoom *= 1.0 + time.delta_seconds();
+    }
+
+    if input.is_key_pressed(KeyCode::KeyX) {
+        camera.zoom /= 1.0 + time.delta_seconds();
+    }
+}
+
+fn main() {
+    App::new("Bella: Camera", 1280, 720)
+        .new_world()
+        .on_start(start)
+        .on_draw(draw)
+        .on_update(update)
+        .run();
+}
@@ -37,27 +37,19 @@ fn draw(mut moving_query: Query<(&MovingObject, &mut Transform)>, mut instance:
 pub fn update(time: Res<Time>, input: Res<Input>, mut transform_query: Query<&mut Transform>) {
     for mut t in &mut transform_query {
         if input.is_key_pressed(KeyCode::KeyW) {
-            t.affine = t
-                .affine
-                .then_translate(Vec2::new(0.0, -100.0 * time.delta_seconds()));
+            t.translate(Vec2::new(0.0, -100.0 * time.delta_seconds()));
         }
 
         if input.is_key_pressed(KeyCode::KeyS) {
-            t.affine = t
-                .affine
-                .then_translate(Vec2::new(0.0, 100.0 * time.delta_seconds()));
+            t.translate(Vec2::new(0.0, 100.0 * time.delta_seconds()));
         }
 
         if input.is_key_pressed(KeyCode::KeyA) {
-            t.affine = t
-                .affine
-                .then_translate(Vec2::new(-100.0 * time.delta_seconds(), 0.0));
+            t.translate(Vec2::new(-100.0 * time.delta_seconds(), 0.0));
         }
 
         if input.is_key_pressed(KeyCode::KeyD) {
-            t.affine = t
-                .affine
-                .then_translate(Vec2::new(100.0 * time.delta_seconds(), 0.0));
+            t.translate(Vec2::new(100.0 * time.delta_seconds(), 0.0));
         }
     }
 }
@@ -0,0 +1,56 @@
+use bella::prelude::*;
+use kurbo::{Affine, Vec2};
+use peniko::{Color, Fill};
+
+fn start(mut instance: ResMut<Instance>) {
+    instance.new_scene("scene");
+}
+
+fn draw_main(mut instance: ResMut<Instance>, input: Res<Input>) {
+    let scene = instance.get_scene("scene").unwrap();
+
+    scene.fill_circle(
+        Fill::NonZero,
+        Affine::translate(*input.mouse_position()),
+        Color::RED,
+        40.0,
+    );
+}
+
+fn draw_inspector(mut instance: ResMut<Instance>) {
+    let resolution = *instance.resolution();
+    let scene = instance.get_scene("scene").unwrap();
+
+    scene.fill_rounded_rect(
+        Fill::NonZero,
+        Affine::translate(resolution / 2.0),
+        Color::YELLOW,
+        Vec2::new(200.0, 100.0),
+        20.0,
+    );
+}
+
+fn update(input: Res<Input>, windows: Res<Windows>, mut inspector: Local<Option<usize>>) {
+    // Moves the inspector world into a brand new window.
+    if input.is_key_just_pressed(KeyCode::KeyO) {
+        *inspector = Some(windows.open("Inspector (Reopened)", 400, 300, &[1]));
+    }
+
+    // Closes the inspector window, which pauses its world until it's reopened.
+    if input.is_key_just_pressed(KeyCode::KeyC) {
+        windows.close(inspector.unwrap_or(1));
+    }
+}
+
+fn main() {
+    App::new("Bella: Multiple Windows", 1280, 720)
+        .new_world()
+        .on_start(start)
+        .on_draw(draw_main)
+        .on_update(update)
+        .new_window("Inspector", 640, 480)
+        .new_world()
+        .on_start(start)
+        .on_draw(draw_inspector)
+        .run();
+}
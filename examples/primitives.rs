@@ -0,0 +1,99 @@
+use bella::prelude::*;
+use kurbo::{Affine, Point, Stroke, Vec2};
+use peniko::{Color, Fill};
+use std::f64::consts::PI;
+
+fn spawn_scene(mut instance: ResMut<Instance>) {
+    instance.new_scene("scene");
+}
+
+fn draw_primitives(mut instance: ResMut<Instance>) {
+    let scene = instance.get_scene("scene").unwrap();
+
+    let stroke = Stroke::new(4.0);
+    let pink = Color::rgb(0.9529, 0.5451, 0.6588);
+    let blue = Color::rgb(0.5373, 0.7059, 0.9804);
+    let at = |x: f64, y: f64| Affine::translate((x, y));
+
+    // First row, filled
+    scene.fill_ellipse(Fill::NonZero, at(100.0, 120.0), pink, Vec2::new(70.0, 40.0));
+    scene.fill_polygon(Fill::NonZero, at(260.0, 120.0), pink, 60.0, 6);
+    scene.fill_star(Fill::NonZero, at(420.0, 120.0), pink, 60.0, 25.0, 5);
+    scene.fill_pie(
+        Fill::NonZero,
+        at(580.0, 120.0),
+        pink,
+        60.0,
+        -PI / 2.0,
+        PI * 1.5,
+    );
+    scene.fill_capsule(
+        Fill::NonZero,
+        at(740.0, 120.0),
+        pink,
+        Vec2::new(120.0, 50.0),
+    );
+
+    // Second row, stroked
+    scene.stroke_ellipse(
+        stroke.clone(),
+        at(100.0, 300.0),
+        blue,
+        Vec2::new(70.0, 40.0),
+    );
+    scene.stroke_polygon(stroke.clone(), at(260.0, 300.0), blue, 60.0, 3);
+    scene.stroke_star(stroke.clone(), at(420.0, 300.0), blue, 60.0, 25.0, 8);
+    scene.stroke_arc(stroke.clone(), at(580.0, 300.0), blue, 60.0, 0.0, PI);
+    scene.stroke_capsule(
+        stroke.clone(),
+        at(740.0, 300.0),
+        blue,
+        Vec2::new(50.0, 120.0),
+    );
+
+    // Lines
+    scene.stroke_line(
+        stroke.clone(),
+        Affine::IDENTITY,
+        Color::WHITE,
+        Point::new(40.0, 450.0),
+        Point::new(200.0, 450.0),
+    );
+    scene.stroke_polyline(
+        stroke.clone(),
+        Affine::IDENTITY,
+        Color::WHITE,
+        &[
+            Point::new(240.0, 500.0),
+            Point::new(290.0, 420.0),
+            Point::new(340.0, 500.0),
+            Point::new(390.0, 420.0),
+        ],
+    );
+    scene.stroke_arrow(
+        stroke.clone(),
+        Affine::IDENTITY,
+        Color::WHITE,
+        Point::new(440.0, 500.0),
+        Point::new(580.0, 420.0),
+        20.0,
+    );
+
+    // A heart, from an SVG path
+    scene
+        .fill_svg_path(
+            Fill::NonZero,
+            at(700.0, 420.0),
+            Color::RED,
+            "M0 20 C-40 -10 -20 -40 0 -20 C20 -40 40 -10 0 20 Z",
+        )
+        .unwrap();
+}
+
+pub fn main() {
+    App::new("Bella: Primitives", 860, 560)
+        .new_world()
+        .on_start(spawn_scene)
+        .on_draw(draw_primitives)
+        .run();
+}
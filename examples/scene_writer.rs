@@ -0,0 +1,47 @@
+use bella::prelude::*;
+use kurbo::{Affine, Vec2};
+use peniko::{Color, Fill};
+
+fn start(mut instance: ResMut<Instance>) {
+    instance.new_scene("scene");
+}
+
+// Both systems only read the Instance, so they can run at the same time.
+fn draw_circles(mut writer: SceneWriter) {
+    let Some(scene) = writer.scene("scene") else {
+        return;
+    };
+
+    for i in 0..10 {
+        scene.fill_circle(
+            Fill::NonZero,
+            Affine::translate((100.0 + i as f64 * 110.0, 200.0)),
+            Color::RED,
+            40.0,
+        );
+    }
+}
+
+fn draw_squares(mut writer: SceneWriter) {
+    let Some(scene) = writer.scene("scene") else {
+        return;
+    };
+
+    for i in 0..10 {
+        scene.fill_rounded_rect(
+            Fill::NonZero,
+            Affine::translate((100.0 + i as f64 * 110.0, 450.0)),
+            Color::YELLOW,
+            Vec2::new(80.0, 80.0),
+            10.0,
+        );
+    }
+}
+
+fn main() {
+    App::new("Bella: Scene Writer", 1280, 720)
+        .new_world()
+        .on_start(start)
+        .on_draw((draw_circles, draw_squares))
+        .run();
+}
@@ -0,0 +1,134 @@
+use bella::prelude::*;
+use kurbo::{Affine, BezPath, RoundedRect, Vec2};
+use peniko::{Color, Extend, Font};
+
+#[derive(Component)]
+struct Spinning;
+
+fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
+    let scene = instance.new_scene("scene");
+
+    let font = instance
+        .asset_server()
+        .load_file::<Font>("examples/assets/FiraSans-Regular.ttf")
+        .unwrap()
+        .clone();
+
+    commands.spawn((
+        Transform::new(Affine::translate((300.0, 300.0))),
+        SceneTarget(scene),
+        ShapeCircle { radius: 80.0 },
+        FillStyle::new(Brush::radial(
+            (0.35, 0.35),
+            1.0,
+            [
+                Color::rgb(1.0, 0.6, 0.6),
+                Color::RED,
+                Color::rgb(0.5, 0.0, 0.0),
+            ],
+        )),
+        StrokeStyle::new(6.0, Color::WHITE),
+        Shadow::glow(12.0, 10.0, Color::rgba(1.0, 0.2, 0.2, 0.8)),
+    ));
+
+    commands
+        .spawn((
+            Spinning,
+            Transform::new(Affine::translate((700.0, 300.0))),
+            SceneTarget(scene),
+            ShapeRoundedRect {
+                size: Vec2::new(200.0, 120.0),
+                corner: 20.0,
+            },
+            FillStyle::new(Brush::horizontal([
+                Color::YELLOW,
+                Color::rgb(1.0, 0.55, 0.0),
+            ])),
+            Shadow::new(Vec2::new(10.0, 14.0), 8.0, Color::rgba(0.0, 0.0, 0.0, 0.6)),
+            // The circle below is cut by the rectangle's corner.
+            ClipShape::from_shape(&RoundedRect::new(-100.0, -60.0, 100.0, 60.0, 20.0)),
+            Opacity(0.8),
+        ))
+        .with_children(|parent| {
+            // Attached to the rectangle, so it spins along with it.
+            parent.spawn((
+                Transform::new(Affine::translate((100.0, 60.0))),
+                SceneTarget(scene),
+                ShapeCircle { radius: 20.0 },
+                FillStyle::new(Color::RED),
+                ZIndex(1),
+            ));
+        });
+
+    let mut triangle = BezPath::new();
+    triangle.move_to((0.0, -70.0));
+    triangle.line_to((70.0, 50.0));
+    triangle.line_to((-70.0, 50.0));
+    triangle.close_path();
+
+    commands.spawn((
+        Transform::new(Affine::translate((1000.0, 300.0))),
+        SceneTarget(scene),
+        ShapePath(triangle),
+        StrokeStyle::new(8.0, Color::rgb(0.5373, 0.7059, 0.9804)),
+    ));
+
+    // A button, with its label centered on it.
+    commands
+        .spawn((
+            Transform::new(Affine::translate((1000.0, 550.0))),
+            SceneTarget(scene),
+            ShapeRoundedRect {
+                size: Vec2::new(220.0, 70.0),
+                corner: 12.0,
+            },
+            FillStyle::new(Color::rgb(0.5373, 0.7059, 0.9804)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Transform::default(),
+                SceneTarget(scene),
+                TextLabel {
+                    text: "Play".to_string(),
+                    font: font.clone(),
+                    size: 36.0,
+                },
+                FillStyle::new(Color::BLACK),
+                Anchor::Center,
+                ZIndex(1),
+            ));
+        });
+
+    commands.spawn((
+        Transform::new(Affine::translate((40.0, 500.0))),
+        SceneTarget(scene),
+        TextLabel {
+            text: "No on_draw systems here!".to_string(),
+            font,
+            size: 60.0,
+        },
+        FillStyle::new(
+            Brush::linear(
+                (0.0, 0.0),
+                (0.1, 0.0),
+                [Color::WHITE, Color::rgb(0.75, 0.75, 0.75)],
+            )
+            .with_extend(Extend::Reflect),
+        ),
+        TextOutline::new(6.0, Color::BLACK),
+    ));
+}
+
+fn update(time: Res<Time>, mut query: Query<&mut Transform, With<Spinning>>) {
+    for mut t in &mut query {
+        t.rotate(time.delta_seconds());
+    }
+}
+
+fn main() {
+    App::new("Bella: Shapes", 1280, 720)
+        .new_world()
+        .on_start(start)
+        .on_update(update)
+        .run();
+}
@@ -1,5 +1,5 @@
 use bella::prelude::*;
-use kurbo::{Affine, Vec2};
+use kurbo::{Affine, Join, Stroke, Vec2};
 use peniko::{Color, Fill, Font};
 use rand::Rng;
 
@@ -10,9 +10,12 @@ struct GameManager {
     score: usize,
     // timer: f64,
     game_font: Font,
+    scene: SceneId,
 }
 
 fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
+    let scene = instance.new_scene("scene");
+
     commands.insert_resource(GameManager {
         target: Vec2::new(300.0, 300.0),
         target_radius: 50.0,
@@ -23,9 +26,8 @@ fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
             .load_file::<Font>("examples/assets/FiraSans-Regular.ttf")
             .unwrap()
             .clone(),
+        scene,
     });
-
-    instance.new_scene("scene");
 }
 
 fn update(mut manager: ResMut<GameManager>, input: Res<Input>, instance: Res<Instance>) {
@@ -43,14 +45,19 @@ fn update(mut manager: ResMut<GameManager>, input: Res<Input>, instance: Res<Ins
 }
 
 fn draw(manager: Res<GameManager>, mut instance: ResMut<Instance>) {
-    let scene = instance.get_scene("scene").unwrap();
+    let score = manager.score.to_string();
+    let width = measure_text(&manager.game_font, &score, 80.0).width;
+    let x = (instance.resolution().x - width) / 2.0;
 
-    scene.fill_text(
-        &manager.score.to_string(),
-        Fill::NonZero,
+    let scene = instance.get_scene(manager.scene).unwrap();
+
+    scene.fill_outlined_text(
+        &score,
         &manager.game_font,
-        Affine::translate(Vec2::new(0.0, 0.0)),
+        Affine::translate(Vec2::new(x, 0.0)),
         Color::WHITE,
+        Stroke::new(6.0).with_join(Join::Round),
+        Color::BLACK,
         80.0,
     );
 
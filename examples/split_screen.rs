@@ -0,0 +1,124 @@
+use bella::prelude::*;
+use kurbo::{Affine, Rect, Vec2};
+use peniko::{Color, Fill};
+
+#[derive(Component)]
+struct Player {
+    up: KeyCode,
+    down: KeyCode,
+    left: KeyCode,
+    right: KeyCode,
+    color: Color,
+}
+
+fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
+    instance.new_scene("world");
+
+    let one = commands
+        .spawn((
+            Player {
+                up: KeyCode::KeyW,
+                down: KeyCode::KeyS,
+                left: KeyCode::KeyA,
+                right: KeyCode::KeyD,
+                color: Color::RED,
+            },
+            Transform::new(Affine::translate((-200.0, 0.0))),
+        ))
+        .id();
+
+    let two = commands
+        .spawn((
+            Player {
+                up: KeyCode::ArrowUp,
+                down: KeyCode::ArrowDown,
+                left: KeyCode::ArrowLeft,
+                right: KeyCode::ArrowRight,
+                color: Color::YELLOW,
+            },
+            Transform::new(Affine::translate((200.0, 0.0))),
+        ))
+        .id();
+
+    // Left and right halves of the window.
+    commands.spawn(
+        Camera2d::default()
+            .with_follow(one)
+            .with_viewport(Viewport::Relative(Rect::new(0.0, 0.0, 0.5, 1.0))),
+    );
+    commands.spawn(
+        Camera2d::default()
+            .with_follow(two)
+            .with_viewport(Viewport::Relative(Rect::new(0.5, 0.0, 1.0, 1.0))),
+    );
+
+    // A minimap on top of both.
+    commands.spawn(
+        Camera2d::default()
+            .with_zoom(0.1)
+            .with_order(1)
+            .with_viewport(Viewport::Pixels(Rect::new(20.0, 20.0, 220.0, 170.0))),
+    );
+}
+
+fn draw(players: Query<(&Transform, &Player)>, mut instance: ResMut<Instance>) {
+    let scene = instance.get_scene("world").unwrap();
+
+    scene.fill_rounded_rect(
+        Fill::NonZero,
+        Affine::IDENTITY,
+        Color::rgb(0.1, 0.1, 0.2),
+        Vec2::new(2000.0, 1500.0),
+        40.0,
+    );
+
+    for x in -10..=10 {
+        for y in -7..=7 {
+            scene.fill_circle(
+                Fill::NonZero,
+                Affine::translate((x as f64 * 100.0, y as f64 * 100.0)),
+                Color::WHITE,
+                4.0,
+            );
+        }
+    }
+
+    for (t, p) in &players {
+        scene.fill_circle(Fill::NonZero, t.affine, p.color, 25.0);
+    }
+}
+
+fn update(time: Res<Time>, input: Res<Input>, mut players: Query<(&mut Transform, &Player)>) {
+    let speed = 300.0 * time.delta_seconds();
+
+    for (mut t, p) in &mut players {
+        let mut direction = Vec2::ZERO;
+
+        if input.is_key_pressed(p.up) {
+            direction.y -= speed;
+        }
+
+        if input.is_key_pressed(p.down) {
+            direction.y += speed;
+        }
+
+        if input.is_key_pressed(p.left) {
+            direction.x -= speed;
+        }
+
+        if input.is_key_pressed(p.right) {
+            direction.x += speed;
+        }
+
+        t.affine = t.affine.then_translate(direction);
+    }
+}
+
+fn main() {
+    App::new("Bella: Split Screen", 1280, 720)
+        .new_world()
+        .on_start(start)
+        .on_draw(draw)
+        .on_update(update)
+        .run();
+}
@@ -0,0 +1,64 @@
+use bella::prelude::*;
+use kurbo::{Affine, Circle, Rect};
+use peniko::{Color, Fill};
+
+#[derive(Component)]
+struct Spinning(f64);
+
+fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
+    let scene = instance.new_scene("scene");
+
+    // Encoded only once, then drawn by every entity below.
+    let sprite = VectorSprite::new(|s| {
+        s.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            Color::rgb(0.5373, 0.7059, 0.9804),
+            None,
+            &Rect::new(-8.0, -8.0, 8.0, 8.0),
+        );
+        s.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            Color::RED,
+            None,
+            &Circle::new((0.0, 0.0), 4.0),
+        );
+    })
+    .with_bounds(Rect::new(-8.0, -8.0, 8.0, 8.0));
+
+    instance.asset_server().insert("square", sprite);
+    let sprite = instance
+        .asset_server()
+        .get::<VectorSprite>("square")
+        .unwrap()
+        .clone();
+
+    for y in 0..36 {
+        for x in 0..64 {
+            commands.spawn((
+                Spinning(1.0 + (x + y) as f64 * 0.05),
+                Transform::new(Affine::translate((
+                    x as f64 * 20.0 + 10.0,
+                    y as f64 * 20.0 + 10.0,
+                ))),
+                SceneTarget(scene),
+                sprite.clone(),
+            ));
+        }
+    }
+}
+
+fn update(time: Res<Time>, mut query: Query<(&mut Transform, &Spinning)>) {
+    for (mut t, s) in &mut query {
+        t.rotate(time.delta_seconds() * s.0);
+    }
+}
+
+fn main() {
+    App::new("Bella: Sprites", 1280, 720)
+        .new_world()
+        .on_start(start)
+        .on_update(update)
+        .run();
+}
@@ -0,0 +1,99 @@
+use bella::prelude::*;
+use kurbo::{Affine, Stroke, Vec2};
+use peniko::{Color, Fill, Font};
+
+const PARAGRAPH: &str = "Text boxes wrap their lines between words, and can align them to the left, the center, the right, or stretch them to fill the whole width. What doesn't fit in the box is cut, with an ellipsis at the end.";
+
+#[derive(Resource)]
+struct Texts {
+    scene: SceneId,
+    layout: TextLayout,
+    rich: TextLayout,
+    boxes: Vec<TextLayout>,
+}
+
+fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
+    let scene = instance.new_scene("scene");
+
+    let font = instance
+        .asset_server()
+        .load_file::<Font>("examples/assets/FiraSans-Regular.ttf")
+        .unwrap()
+        .clone();
+
+    // The text is shaped only once, and then drawn every frame.
+    // Add more fonts after the first one to draw the characters it doesn't have.
+    let layout = TextLayout::new(
+        "Kerning: AV Wa To\nLigatures: office affine\nMarks: e\u{301} n\u{303} a\u{308}",
+        std::slice::from_ref(&font),
+        40.0,
+    );
+
+    // Parts of a text can have their own size, color, weight and decorations.
+    let markup = "<b>Rich text</b>: <color=#ff8000>colors</color>, <size=30>sizes</size>, <u>underlines</u> and <s>strikethroughs</s>";
+    let rich = TextLayout::rich(
+        &RichText::parse(markup).unwrap(),
+        std::slice::from_ref(&font),
+        24.0,
+    );
+
+    let paragraph = |align| {
+        TextLayout::new(PARAGRAPH, std::slice::from_ref(&font), 20.0)
+            .with_max_width(260.0)
+            .with_max_height(200.0)
+            .with_align(align)
+            .with_ellipsis(true)
+    };
+
+    let boxes = vec![
+        paragraph(TextAlign::Left),
+        paragraph(TextAlign::Center),
+        paragraph(TextAlign::Right),
+        paragraph(TextAlign::Justify).with_line_spacing(1.4),
+    ];
+
+    commands.insert_resource(Texts {
+        scene,
+        layout,
+        rich,
+        boxes,
+    });
+}
+
+fn draw(texts: Res<Texts>, mut instance: ResMut<Instance>) {
+    let scene = instance.get_scene(texts.scene).unwrap();
+
+    scene.fill_layout(
+        Fill::NonZero,
+        &texts.layout,
+        Affine::translate((40.0, 40.0)),
+        Color::WHITE,
+    );
+
+    scene.fill_layout(
+        Fill::NonZero,
+        &texts.rich,
+        Affine::translate((40.0, 220.0)),
+        Color::WHITE,
+    );
+
+    for (i, layout) in texts.boxes.iter().enumerate() {
+        let t = Affine::translate((40.0 + i as f64 * 300.0, 300.0));
+
+        scene.stroke_shape(
+            &Stroke::new(1.0),
+            t.then_translate(Vec2::new(0.0, layout.size())),
+            Color::rgb(0.4, 0.4, 0.5),
+            &layout.bounds,
+        );
+        scene.fill_layout(Fill::NonZero, layout, t, Color::WHITE);
+    }
+}
+
+fn main() {
+    App::new("Text", 1280, 720)
+        .new_world()
+        .on_start(start)
+        .on_draw(draw)
+        .run();
+}
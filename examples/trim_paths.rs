@@ -0,0 +1,70 @@
+use bella::prelude::*;
+use kurbo::{Affine, BezPath, Vec2};
+use peniko::Color;
+
+#[derive(Component)]
+struct Spinner;
+
+#[derive(Component)]
+struct LoadingBar;
+
+fn start(mut commands: Commands, mut instance: ResMut<Instance>) {
+    let scene = instance.new_scene("scene");
+
+    // A quarter of a ring, running around it.
+    commands.spawn((
+        Spinner,
+        Transform::new(Affine::translate((250.0, 250.0))),
+        SceneTarget(scene),
+        ShapeCircle { radius: 80.0 },
+        StrokeStyle::new(12.0, Color::rgb(0.5373, 0.7059, 0.9804)),
+        Trim::new(0.0, 0.25),
+    ));
+
+    // A loading bar, drawn from left to right.
+    let mut bar = BezPath::new();
+    bar.move_to((-200.0, 0.0));
+    bar.line_to((200.0, 0.0));
+
+    commands.spawn((
+        LoadingBar,
+        Transform::new(Affine::translate((700.0, 250.0))),
+        SceneTarget(scene),
+        ShapePath(bar),
+        StrokeStyle::new(20.0, Brush::horizontal([Color::RED, Color::YELLOW])),
+        Trim::progress(0.0),
+    ));
+
+    // Dashes don't need a trim.
+    commands.spawn((
+        Transform::new(Affine::translate((250.0, 550.0))),
+        SceneTarget(scene),
+        ShapeRoundedRect {
+            size: Vec2::new(300.0, 120.0),
+            corner: 20.0,
+        },
+        StrokeStyle::new(4.0, Color::WHITE).with_dashes(0.0, [16.0, 8.0]),
+    ));
+}
+
+fn update(
+    time: Res<Time>,
+    mut spinners: Query<&mut Trim, (With<Spinner>, Without<LoadingBar>)>,
+    mut bars: Query<&mut Trim, With<LoadingBar>>,
+) {
+    for mut trim in &mut spinners {
+        trim.offset = (trim.offset + time.delta_seconds()).fract();
+    }
+
+    for mut trim in &mut bars {
+        trim.end = (trim.end + time.delta_seconds() * 0.2) % 1.0;
+    }
+}
+
+fn main() {
+    App::new("Bella: Trim Paths", 1000, 720)
+        .new_world()
+        .on_start(start)
+        .on_update(update)
+        .run();
+}
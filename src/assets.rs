@@ -1,9 +1,18 @@
 use anymap::Map;
-use core::{any::Any, fmt::Debug};
-use std::{collections::HashMap, fs, io::Result, sync::Arc};
+use core::{
+    any::Any,
+    fmt::{self, Debug},
+};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Result},
+    sync::Arc,
+};
 use vello::peniko::{Blob, Font};
 use vello::skrifa::raw::FileRef;
-use vello::skrifa::FontRef;
+use vello::skrifa::string::StringId;
+use vello::skrifa::{FontRef, MetadataProvider};
 
 pub type AnyMap = Map<dyn Any + Send + Sync + 'static>;
 
@@ -39,6 +48,33 @@ impl AssetServer {
                 }),
         )
     }
+
+    /// Loads a font from a collection (`.ttc` or `.otc`), which can be found later as `url#index`, like `fonts/Noto.ttc#2`. A font that isn't a collection only has the face 0.
+    pub fn load_font(
+        &mut self,
+        url: &str,
+        face: FontFace,
+    ) -> core::result::Result<&mut Font, FontError> {
+        let font = load_font_face(url, face)?;
+        Ok(self.insert(&format!("{url}#{}", font.index), font))
+    }
+
+    /// Stores an asset that wasn't loaded from a file (like a [`VectorSprite`](crate::sprites::VectorSprite)), so it can be found later by its name.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, name: &str, asset: T) -> &mut T {
+        if self.assets.get::<HashMap<String, T>>().is_none() {
+            self.assets.insert(HashMap::<String, T>::new());
+        }
+
+        let asset_list = self.assets.get_mut::<HashMap<String, T>>().unwrap();
+
+        asset_list.insert(name.to_string(), asset);
+        asset_list.get_mut(name).unwrap()
+    }
+
+    /// Gets an asset that was already loaded or inserted.
+    pub fn get<T: Send + Sync + 'static>(&self, name: &str) -> Option<&T> {
+        self.assets.get::<HashMap<String, T>>()?.get(name)
+    }
 }
 
 // ================================
@@ -60,15 +96,39 @@ impl Format for TextFile {
     }
 }
 
+/// Loads the first font of the file. The other fonts of a collection are loaded with [`AssetServer::load_font`].
 impl Format for Font {
     fn load_file(url: &str) -> Result<Self> {
-        match fs::read(url) {
-            Ok(bytes) => Ok(Font::new(Blob::new(Arc::new(bytes)), 0)),
-            Err(e) => Err(e),
+        match load_font_face(url, FontFace::Index(0)) {
+            Ok(font) => Ok(font),
+            Err(FontError::Io(e)) => Err(e),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
         }
     }
 }
 
+/// Reads a font file and picks one of its fonts.
+fn load_font_face(url: &str, face: FontFace) -> core::result::Result<Font, FontError> {
+    let font = Font::new(Blob::new(Arc::new(fs::read(url)?)), 0);
+    let names = font.face_names();
+
+    let index = match face {
+        FontFace::Index(i) => Some(i).filter(|i| (*i as usize) < names.len()),
+        FontFace::Name(name) => names.iter().position(|n| n == name).map(|i| i as u32),
+    };
+
+    match index {
+        Some(index) => Ok(Font::new(font.data, index)),
+        None => Err(FontError::FaceNotFound {
+            url: url.to_string(),
+            face: match face {
+                FontFace::Index(i) => i.to_string(),
+                FontFace::Name(name) => name.to_string(),
+            },
+        }),
+    }
+}
+
 pub trait ToFontRef: Debug + Send + Sync {
     fn to_font_ref(&self) -> Option<FontRef<'_>>;
 }
@@ -82,3 +142,102 @@ impl ToFontRef for Font {
         }
     }
 }
+
+/// Which font to load from a collection, with [`AssetServer::load_font`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontFace<'a> {
+    Index(u32),
+    /// The full name of the font, like `Noto Sans CJK JP Bold`, as listed by [`FontInfo::face_names`].
+    Name(&'a str),
+}
+
+/// Why a font couldn't be loaded by [`AssetServer::load_font`].
+#[derive(Debug)]
+pub enum FontError {
+    Io(io::Error),
+    /// The file has no font with this index or name, or isn't a font at all.
+    FaceNotFound {
+        url: String,
+        face: String,
+    },
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::FaceNotFound { url, face } => write!(f, "{url:?} has no font {face:?}"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+impl From<io::Error> for FontError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// A variation axis of a font, like its weight or its width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontAxis {
+    /// Like `wght`, `wdth`, `slnt`, or a custom one like `GRAD`.
+    pub tag: String,
+    pub name: Option<String>,
+    pub min: f32,
+    pub default: f32,
+    pub max: f32,
+}
+
+/// What's in a font file: the variation axes of a font, and the fonts of a collection.
+pub trait FontInfo {
+    /// The axes that can be set with [`TextStyle`](crate::rich_text::TextStyle), empty if the font isn't variable.
+    fn axes(&self) -> Vec<FontAxis>;
+
+    /// The full names of every font in the file, by index. A font that isn't a collection only has one.
+    fn face_names(&self) -> Vec<String>;
+}
+
+impl FontInfo for Font {
+    fn axes(&self) -> Vec<FontAxis> {
+        let Some(font) = self.to_font_ref() else {
+            return vec![];
+        };
+
+        font.axes()
+            .iter()
+            .map(|axis| FontAxis {
+                tag: axis.tag().to_string(),
+                name: font_string(&font, axis.name_id()),
+                min: axis.min_value(),
+                default: axis.default_value(),
+                max: axis.max_value(),
+            })
+            .collect()
+    }
+
+    fn face_names(&self) -> Vec<String> {
+        let fonts: Vec<Option<FontRef>> = match FileRef::new(self.data.as_ref()) {
+            Ok(FileRef::Font(font)) => vec![Some(font)],
+            Ok(FileRef::Collection(collection)) => collection.iter().map(|f| f.ok()).collect(),
+            Err(_) => vec![],
+        };
+
+        fonts
+            .iter()
+            .map(|font| {
+                font.as_ref()
+                    .and_then(|f| {
+                        font_string(f, StringId::FULL_NAME)
+                            .or_else(|| font_string(f, StringId::FAMILY_NAME))
+                    })
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+fn font_string(font: &FontRef, id: StringId) -> Option<String> {
+    Some(font.localized_strings(id).english_or_first()?.to_string())
+}
@@ -1,10 +1,177 @@
-use crate::assets::ToFontRef;
-use vello::kurbo::{Affine, Circle, Point, RoundedRect, Stroke, Vec2};
-use vello::peniko::{BrushRef, Fill, Font, Style};
-use vello::skrifa::MetadataProvider;
-use vello::{Glyph, Scene};
+use crate::brush::Brush;
+use crate::rich_text::{RichText, TextStyle};
+use crate::shapes::Shadow;
+use crate::text::TextLayout;
+use crate::trim::Trim;
+use bevy_ecs::prelude::Component;
+use vello::kurbo::{
+    Affine, Arc, BezPath, Circle, CircleSegment, Ellipse, Line, Point, Rect, RoundedRect, Shape,
+    Stroke, SvgParseError, Vec2,
+};
+use vello::peniko::{BlendMode, BrushRef, Color, Fill, Font, Style};
+use vello::Scene;
+
+/// Where an anchor places the origin of what's drawn, relative to its bounds.
+///
+/// Without an anchor, every [`SceneBasics`] helper keeps its own origin: shapes are centered on it, and texts start from their top-left corner.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+    /// A fraction of the bounds, `(0.0, 0.0)` being the top-left corner and `(1.0, 1.0)` the bottom-right one.
+    Custom(Vec2),
+}
+
+impl Anchor {
+    pub fn fraction(&self) -> Vec2 {
+        match self {
+            Anchor::TopLeft => Vec2::new(0.0, 0.0),
+            Anchor::Top => Vec2::new(0.5, 0.0),
+            Anchor::TopRight => Vec2::new(1.0, 0.0),
+            Anchor::Left => Vec2::new(0.0, 0.5),
+            Anchor::Center => Vec2::new(0.5, 0.5),
+            Anchor::Right => Vec2::new(1.0, 0.5),
+            Anchor::BottomLeft => Vec2::new(0.0, 1.0),
+            Anchor::Bottom => Vec2::new(0.5, 1.0),
+            Anchor::BottomRight => Vec2::new(1.0, 1.0),
+            Anchor::Custom(f) => *f,
+        }
+    }
+
+    /// The translation that moves the anchor point of `bounds` onto the origin.
+    pub fn offset(&self, bounds: Rect) -> Vec2 {
+        let f = self.fraction();
+        -Vec2::new(
+            bounds.x0 + bounds.width() * f.x,
+            bounds.y0 + bounds.height() * f.y,
+        )
+    }
+
+    /// Moves something with these `bounds` so its anchor point ends up on the origin of `t`.
+    pub fn apply(&self, t: Affine, bounds: Rect) -> Affine {
+        t.pre_translate(self.offset(bounds))
+    }
+}
+
+/// What the [`SceneBasics`] helpers draw into.
+pub trait DrawTarget {
+    fn scene(&mut self) -> &mut Scene;
+
+    /// The anchor of everything drawn, `None` keeping the origin of each helper.
+    fn anchor(&self) -> Option<Anchor>;
+
+    /// The part of the strokes that's drawn, `None` drawing them whole.
+    fn trim(&self) -> Option<Trim> {
+        None
+    }
+
+    fn anchored_transform(&self, t: Affine, bounds: Rect) -> Affine {
+        self.anchor().map_or(t, |a| a.apply(t, bounds))
+    }
+}
+
+impl DrawTarget for Scene {
+    fn scene(&mut self) -> &mut Scene {
+        self
+    }
+
+    fn anchor(&self) -> Option<Anchor> {
+        None
+    }
+}
+
+/// A scene where everything is drawn from an [`Anchor`], returned by [`SceneBasics::anchored`].
+///
+/// ```ignore
+/// scene
+///     .anchored(Anchor::TopLeft)
+///     .fill_rounded_rect(Fill::NonZero, t, Color::WHITE, size, 8.0);
+/// ```
+pub struct Anchored<'a, T> {
+    target: &'a mut T,
+    anchor: Anchor,
+}
+
+impl<T: DrawTarget> DrawTarget for Anchored<'_, T> {
+    fn scene(&mut self) -> &mut Scene {
+        self.target.scene()
+    }
+
+    fn anchor(&self) -> Option<Anchor> {
+        Some(self.anchor)
+    }
+
+    fn trim(&self) -> Option<Trim> {
+        self.target.trim()
+    }
+}
+
+/// A scene where only a part of every stroke is drawn, returned by [`SceneBasics::trimmed`].
+///
+/// ```ignore
+/// scene
+///     .trimmed(Trim::progress(0.75))
+///     .stroke_circle(Stroke::new(8.0), t, Color::WHITE, 40.0);
+/// ```
+pub struct Trimmed<'a, T> {
+    target: &'a mut T,
+    trim: Trim,
+}
+
+impl<T: DrawTarget> DrawTarget for Trimmed<'_, T> {
+    fn scene(&mut self) -> &mut Scene {
+        self.target.scene()
+    }
+
+    fn anchor(&self) -> Option<Anchor> {
+        self.target.anchor()
+    }
+
+    fn trim(&self) -> Option<Trim> {
+        Some(self.trim)
+    }
+}
 
 pub trait SceneBasics {
+    /// Fills any shape, from the anchor if there's one.
+    fn fill_shape<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        shape: &impl Shape,
+    );
+    /// Strokes any shape, from the anchor if there's one, and trimmed if there's a trim.
+    fn stroke_shape<'b>(
+        &mut self,
+        s: &Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        shape: &impl Shape,
+    );
+
+    /// Fills any shape with a [`Brush`] placed relative to the bounds of the shape.
+    fn fill_brush(&mut self, f: Fill, t: Affine, brush: &Brush, shape: &impl Shape);
+    fn stroke_brush(&mut self, s: &Stroke, t: Affine, brush: &Brush, shape: &impl Shape);
+
+    /// Draws with every helper placed from `anchor` instead of its own origin.
+    fn anchored(&mut self, anchor: Anchor) -> Anchored<'_, Self>
+    where
+        Self: Sized;
+
+    /// Draws only a part of every stroke, see [`Trim`].
+    fn trimmed(&mut self, trim: Trim) -> Trimmed<'_, Self>
+    where
+        Self: Sized;
+
     fn fill_circle<'b>(&mut self, f: Fill, t: Affine, b: impl Into<BrushRef<'b>>, radius: f64);
     fn fill_rounded_rect<'b>(
         &mut self,
@@ -25,6 +192,125 @@ pub trait SceneBasics {
         corner: f64,
     );
 
+    fn fill_ellipse<'b>(&mut self, f: Fill, t: Affine, b: impl Into<BrushRef<'b>>, radii: Vec2);
+    fn stroke_ellipse<'b>(&mut self, s: Stroke, t: Affine, b: impl Into<BrushRef<'b>>, radii: Vec2);
+
+    /// A polygon with `sides` sides of the same length, with its first corner pointing up.
+    fn fill_polygon<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        sides: usize,
+    );
+    fn stroke_polygon<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        sides: usize,
+    );
+
+    /// A star with `points` points, going between the `outer` and `inner` radiuses.
+    fn fill_star<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        outer: f64,
+        inner: f64,
+        points: usize,
+    );
+    fn stroke_star<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        outer: f64,
+        inner: f64,
+        points: usize,
+    );
+
+    /// A slice of a circle, starting at `start` (in radians, `0.0` points right) and going clockwise for `sweep` radians.
+    fn fill_pie<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    );
+    fn stroke_pie<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    );
+
+    /// A part of the outline of a circle, with the same angles as [`SceneBasics::fill_pie`].
+    fn stroke_arc<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    );
+
+    /// A rectangle with fully rounded ends.
+    fn fill_capsule<'b>(&mut self, f: Fill, t: Affine, b: impl Into<BrushRef<'b>>, size: Vec2);
+    fn stroke_capsule<'b>(&mut self, s: Stroke, t: Affine, b: impl Into<BrushRef<'b>>, size: Vec2);
+
+    fn stroke_line<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        from: Point,
+        to: Point,
+    );
+    fn stroke_polyline<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        points: &[Point],
+    );
+
+    /// A line from `from` to `to`, with a head of `head_size` at `to`.
+    fn stroke_arrow<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        from: Point,
+        to: Point,
+        head_size: f64,
+    );
+
+    /// A path written like the `d` attribute of an SVG `<path>`.
+    fn fill_svg_path<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        path: &str,
+    ) -> Result<(), SvgParseError>;
+    fn stroke_svg_path<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        path: &str,
+    ) -> Result<(), SvgParseError>;
+
     fn fill_text<'b>(
         &mut self,
         text: &str,
@@ -34,11 +320,153 @@ pub trait SceneBasics {
         b: impl Into<BrushRef<'b>>,
         font_size: f64,
     );
+
+    /// Like [`SceneBasics::fill_text`], with a [`TextStyle`] for the axes of variable fonts, the decorations, or a color replacing the brush.
+    #[allow(clippy::too_many_arguments)]
+    fn fill_styled_text<'b>(
+        &mut self,
+        text: &str,
+        fill: Fill,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        font_size: f64,
+        style: TextStyle,
+    );
+
+    /// Draws a text shaped by a [`TextLayout`], with its first line at `t`.
+    fn fill_layout<'b>(
+        &mut self,
+        fill: Fill,
+        layout: &TextLayout,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+    );
+
+    /// Draws the outline of a text.
+    fn stroke_text<'b>(
+        &mut self,
+        text: &str,
+        s: Stroke,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        font_size: f64,
+    );
+
+    /// Draws the outline of a text shaped by a [`TextLayout`], with its first line at `t`.
+    fn stroke_layout<'b>(
+        &mut self,
+        s: Stroke,
+        layout: &TextLayout,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+    );
+
+    /// Fills a text over its outline, which keeps it readable on any background. Only the outer half of the stroke shows, so it looks half as wide as `s`.
+    #[allow(clippy::too_many_arguments)]
+    fn fill_outlined_text<'b, 'c>(
+        &mut self,
+        text: &str,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        s: Stroke,
+        outline: impl Into<BrushRef<'c>>,
+        font_size: f64,
+    );
+
+    /// Like [`SceneBasics::fill_outlined_text`], with a text shaped by a [`TextLayout`].
+    fn fill_outlined_layout<'b, 'c>(
+        &mut self,
+        layout: &TextLayout,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        s: Stroke,
+        outline: impl Into<BrushRef<'c>>,
+    );
+
+    /// Draws a text whose spans have their own styles. `font`, `font_size` and the brush are used by the spans that don't set theirs.
+    fn fill_rich_text<'b>(
+        &mut self,
+        fill: Fill,
+        text: &RichText,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        font_size: f64,
+    );
+
+    /// Draws a blurred rounded rectangle centered on `t`, to be used as a shadow. `blur` is the standard deviation of the blur.
+    fn fill_shadow_rect(&mut self, t: Affine, size: Vec2, corner: f64, color: Color, blur: f64);
+
+    /// Draws a [`Shadow`] under a rounded rectangle centered on `t`, moved by its offset and grown by its spread.
+    fn fill_shadow(&mut self, t: Affine, size: Vec2, corner: f64, shadow: &Shadow);
+
+    /// Draws a blurred circle centered on `t`, that looks like a glow.
+    fn fill_glow_circle(&mut self, t: Affine, radius: f64, color: Color, blur: f64);
+
+    /// Draws everything done in `f` inside a layer, clipped to `clip` and composited with `blend` and `alpha`.
+    fn with_layer(
+        &mut self,
+        blend: impl Into<BlendMode>,
+        alpha: f32,
+        t: Affine,
+        clip: &impl Shape,
+        f: impl FnOnce(&mut Self),
+    );
 }
 
-impl SceneBasics for Scene {
+impl<T: DrawTarget> SceneBasics for T {
+    fn fill_shape<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        shape: &impl Shape,
+    ) {
+        let t = self.anchored_transform(t, shape.bounding_box());
+        self.scene().fill(f, t, b, None, shape);
+    }
+
+    fn stroke_shape<'b>(
+        &mut self,
+        s: &Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        shape: &impl Shape,
+    ) {
+        let t = self.anchored_transform(t, shape.bounding_box());
+
+        match self.trim() {
+            Some(trim) => self.scene().stroke(s, t, b, None, &trim.apply(shape)),
+            None => self.scene().stroke(s, t, b, None, shape),
+        }
+    }
+
+    fn fill_brush(&mut self, f: Fill, t: Affine, brush: &Brush, shape: &impl Shape) {
+        let brush = brush.resolve(shape.bounding_box());
+        self.fill_shape(f, t, &brush, shape);
+    }
+
+    fn stroke_brush(&mut self, s: &Stroke, t: Affine, brush: &Brush, shape: &impl Shape) {
+        let brush = brush.resolve(shape.bounding_box());
+        self.stroke_shape(s, t, &brush, shape);
+    }
+
+    fn anchored(&mut self, anchor: Anchor) -> Anchored<'_, Self> {
+        Anchored {
+            target: self,
+            anchor,
+        }
+    }
+
+    fn trimmed(&mut self, trim: Trim) -> Trimmed<'_, Self> {
+        Trimmed { target: self, trim }
+    }
+
     fn fill_circle<'b>(&mut self, f: Fill, t: Affine, b: impl Into<BrushRef<'b>>, radius: f64) {
-        self.fill(f, t, b, None, &Circle::new(Point::new(0.0, 0.0), radius));
+        self.fill_shape(f, t, b, &Circle::new(Point::new(0.0, 0.0), radius));
     }
 
     fn fill_rounded_rect<'b>(
@@ -49,11 +477,10 @@ impl SceneBasics for Scene {
         size: Vec2,
         corner: f64,
     ) {
-        self.fill(
+        self.fill_shape(
             f,
             t,
             b,
-            None,
             &RoundedRect::new(
                 -(size.x / 2.0),
                 -(size.y / 2.0),
@@ -73,61 +500,100 @@ impl SceneBasics for Scene {
         b: impl Into<BrushRef<'b>>,
         font_size: f64,
     ) {
-        let font_ref = font.to_font_ref().unwrap();
-        let axes = font_ref.axes();
-        let charmap = font_ref.charmap();
-        let final_font_size = vello::skrifa::instance::Size::new(font_size as f32);
-        let variations: &[(&str, f32)] = &[];
-        let var_loc = axes.location(variations.iter().copied());
-        let metrics = font_ref.metrics(final_font_size, &var_loc);
-        let line_height = metrics.ascent - metrics.descent + metrics.leading;
-        let glyph_metrics = font_ref.glyph_metrics(final_font_size, &var_loc);
-        let mut pen_x = 0f32;
-        let mut pen_y = 0f32;
-
-        let gly: Vec<Glyph> = text
-            .to_string()
-            .chars()
-            .filter_map(|ch| {
-                if ch == '\n' {
-                    pen_y += line_height;
-                    pen_x = 0.0;
-                    return None;
-                }
-                let gid = charmap.map(ch).unwrap_or_default();
-                let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-                let x = pen_x;
-                pen_x += advance;
-                Some(Glyph {
-                    id: gid.to_u32(),
-                    x,
-                    y: pen_y,
-                })
-            })
-            .collect();
-
-        // scene
-        //     .draw_glyphs(&get_font)
-        //     .font_size(get_font_size)
-        //     .transform(Affine::IDENTITY)
-        //     .glyph_transform(None)
-        //     .normalized_coords(var_loc.coords())
-        //     .brush(&get_style.stroke_brush.to_peniko_brush())
-        //     .hint(false)
-        //     .draw(&peniko::Style::Stroke(get_style.stroke), gly.clone().into_iter());
-
-        self.draw_glyphs(font)
-            .font_size(font_size as f32)
-            .transform(t.then_translate(Vec2::new(0.0, font_size)))
-            .glyph_transform(None)
-            .normalized_coords(var_loc.coords())
-            .brush(b.into())
-            .hint(false)
-            .draw(&Style::Fill(fill), gly.into_iter());
+        let layout = TextLayout::new(text, std::slice::from_ref(font), font_size);
+        self.fill_layout(fill, &layout, t, b);
+    }
+
+    fn fill_styled_text<'b>(
+        &mut self,
+        text: &str,
+        fill: Fill,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        font_size: f64,
+        style: TextStyle,
+    ) {
+        let layout = TextLayout::styled(text, std::slice::from_ref(font), font_size, style);
+        self.fill_layout(fill, &layout, t, b);
+    }
+
+    fn fill_layout<'b>(
+        &mut self,
+        fill: Fill,
+        layout: &TextLayout,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+    ) {
+        let t = layout_transform(self, t, layout);
+        draw_layout(self.scene(), &Style::Fill(fill), layout, t, b.into());
+    }
+
+    fn stroke_text<'b>(
+        &mut self,
+        text: &str,
+        s: Stroke,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        font_size: f64,
+    ) {
+        let layout = TextLayout::new(text, std::slice::from_ref(font), font_size);
+        self.stroke_layout(s, &layout, t, b);
+    }
+
+    fn stroke_layout<'b>(
+        &mut self,
+        s: Stroke,
+        layout: &TextLayout,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+    ) {
+        let t = layout_transform(self, t, layout);
+        draw_layout(self.scene(), &Style::Stroke(s), layout, t, b.into());
+    }
+
+    fn fill_outlined_text<'b, 'c>(
+        &mut self,
+        text: &str,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        s: Stroke,
+        outline: impl Into<BrushRef<'c>>,
+        font_size: f64,
+    ) {
+        let layout = TextLayout::new(text, std::slice::from_ref(font), font_size);
+        self.fill_outlined_layout(&layout, t, b, s, outline);
+    }
+
+    fn fill_outlined_layout<'b, 'c>(
+        &mut self,
+        layout: &TextLayout,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        s: Stroke,
+        outline: impl Into<BrushRef<'c>>,
+    ) {
+        self.stroke_layout(s, layout, t, outline);
+        self.fill_layout(Fill::NonZero, layout, t, b);
+    }
+
+    fn fill_rich_text<'b>(
+        &mut self,
+        fill: Fill,
+        text: &RichText,
+        font: &Font,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        font_size: f64,
+    ) {
+        let layout = TextLayout::rich(text, std::slice::from_ref(font), font_size);
+        self.fill_layout(fill, &layout, t, b);
     }
 
     fn stroke_circle<'b>(&mut self, s: Stroke, t: Affine, b: impl Into<BrushRef<'b>>, radius: f64) {
-        self.stroke(&s, t, b, None, &Circle::new(Point::new(0.0, 0.0), radius));
+        self.stroke_shape(&s, t, b, &Circle::new(Point::new(0.0, 0.0), radius));
     }
 
     fn stroke_rounded_rect<'b>(
@@ -138,11 +604,10 @@ impl SceneBasics for Scene {
         size: Vec2,
         corner: f64,
     ) {
-        self.stroke(
+        self.stroke_shape(
             &s,
             t,
             b,
-            None,
             &RoundedRect::new(
                 -(size.x / 2.0),
                 -(size.y / 2.0),
@@ -152,4 +617,333 @@ impl SceneBasics for Scene {
             ),
         );
     }
+
+    fn fill_ellipse<'b>(&mut self, f: Fill, t: Affine, b: impl Into<BrushRef<'b>>, radii: Vec2) {
+        self.fill_shape(f, t, b, &Ellipse::new(Point::ZERO, radii, 0.0));
+    }
+
+    fn stroke_ellipse<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radii: Vec2,
+    ) {
+        self.stroke_shape(&s, t, b, &Ellipse::new(Point::ZERO, radii, 0.0));
+    }
+
+    fn fill_polygon<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        sides: usize,
+    ) {
+        self.fill_shape(f, t, b, &regular_polygon(radius, sides));
+    }
+
+    fn stroke_polygon<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        sides: usize,
+    ) {
+        self.stroke_shape(&s, t, b, &regular_polygon(radius, sides));
+    }
+
+    fn fill_star<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        outer: f64,
+        inner: f64,
+        points: usize,
+    ) {
+        self.fill_shape(f, t, b, &star(outer, inner, points));
+    }
+
+    fn stroke_star<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        outer: f64,
+        inner: f64,
+        points: usize,
+    ) {
+        self.stroke_shape(&s, t, b, &star(outer, inner, points));
+    }
+
+    fn fill_pie<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    ) {
+        let pie = CircleSegment::new(Point::ZERO, radius, 0.0, start, sweep);
+        self.fill_shape(f, t, b, &pie);
+    }
+
+    fn stroke_pie<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    ) {
+        let pie = CircleSegment::new(Point::ZERO, radius, 0.0, start, sweep);
+        self.stroke_shape(&s, t, b, &pie);
+    }
+
+    fn stroke_arc<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    ) {
+        let arc = Arc::new(Point::ZERO, (radius, radius), start, sweep, 0.0);
+        self.stroke_shape(&s, t, b, &arc);
+    }
+
+    fn fill_capsule<'b>(&mut self, f: Fill, t: Affine, b: impl Into<BrushRef<'b>>, size: Vec2) {
+        self.fill_rounded_rect(f, t, b, size, size.x.min(size.y) / 2.0);
+    }
+
+    fn stroke_capsule<'b>(&mut self, s: Stroke, t: Affine, b: impl Into<BrushRef<'b>>, size: Vec2) {
+        self.stroke_rounded_rect(s, t, b, size, size.x.min(size.y) / 2.0);
+    }
+
+    fn stroke_line<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        from: Point,
+        to: Point,
+    ) {
+        self.stroke_shape(&s, t, b, &Line::new(from, to));
+    }
+
+    fn stroke_polyline<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        points: &[Point],
+    ) {
+        self.stroke_shape(&s, t, b, &polyline(points));
+    }
+
+    fn stroke_arrow<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        from: Point,
+        to: Point,
+        head_size: f64,
+    ) {
+        self.stroke_shape(&s, t, b, &arrow(from, to, head_size));
+    }
+
+    fn fill_svg_path<'b>(
+        &mut self,
+        f: Fill,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        path: &str,
+    ) -> Result<(), SvgParseError> {
+        self.fill_shape(f, t, b, &BezPath::from_svg(path)?);
+        Ok(())
+    }
+
+    fn stroke_svg_path<'b>(
+        &mut self,
+        s: Stroke,
+        t: Affine,
+        b: impl Into<BrushRef<'b>>,
+        path: &str,
+    ) -> Result<(), SvgParseError> {
+        self.stroke_shape(&s, t, b, &BezPath::from_svg(path)?);
+        Ok(())
+    }
+
+    fn fill_shadow_rect(&mut self, t: Affine, size: Vec2, corner: f64, color: Color, blur: f64) {
+        self.fill_shadow(t, size, corner, &Shadow::new(Vec2::ZERO, blur, color));
+    }
+
+    fn fill_shadow(&mut self, t: Affine, size: Vec2, corner: f64, shadow: &Shadow) {
+        let rect = Rect::new(-(size.x / 2.0), -(size.y / 2.0), size.x / 2.0, size.y / 2.0);
+
+        // The shadow is anchored like its shape, and only then grown by its spread, so that they stay centered on each other.
+        let t = self
+            .anchored_transform(t, rect)
+            .pre_translate(shadow.offset);
+
+        self.scene().draw_blurred_rounded_rect(
+            t,
+            rect.inflate(shadow.spread, shadow.spread),
+            shadow.color,
+            corner + shadow.spread,
+            shadow.blur,
+        );
+    }
+
+    fn fill_glow_circle(&mut self, t: Affine, radius: f64, color: Color, blur: f64) {
+        self.fill_shadow_rect(
+            t,
+            Vec2::new(radius * 2.0, radius * 2.0),
+            radius,
+            color,
+            blur,
+        );
+    }
+
+    fn with_layer(
+        &mut self,
+        blend: impl Into<BlendMode>,
+        alpha: f32,
+        t: Affine,
+        clip: &impl Shape,
+        f: impl FnOnce(&mut Self),
+    ) {
+        self.scene().push_layer(blend, alpha, t, clip);
+        f(self);
+        self.scene().pop_layer();
+    }
+}
+
+/// A regular polygon centered on the origin, with its first corner pointing up.
+pub fn regular_polygon(radius: f64, sides: usize) -> BezPath {
+    let sides = sides.max(3);
+    let step = std::f64::consts::TAU / sides as f64;
+
+    closed_path((0..sides).map(|i| corner(radius, i as f64 * step)))
+}
+
+/// A star centered on the origin, with its first point pointing up.
+pub fn star(outer: f64, inner: f64, points: usize) -> BezPath {
+    let points = points.max(2);
+    let step = std::f64::consts::PI / points as f64;
+
+    closed_path((0..points * 2).map(|i| {
+        let radius = if i % 2 == 0 { outer } else { inner };
+        corner(radius, i as f64 * step)
+    }))
+}
+
+/// Lines going through every point, without closing the path.
+pub fn polyline(points: &[Point]) -> BezPath {
+    let mut path = BezPath::new();
+
+    for (i, p) in points.iter().enumerate() {
+        if i == 0 {
+            path.move_to(*p);
+        } else {
+            path.line_to(*p);
+        }
+    }
+
+    path
+}
+
+/// A line from `from` to `to`, with two more lines forming a head at `to`.
+pub fn arrow(from: Point, to: Point, head_size: f64) -> BezPath {
+    let mut path = polyline(&[from, to]);
+    let direction = (to - from).normalize();
+
+    if direction.is_finite() {
+        let back = to - direction * head_size;
+        let side = Vec2::new(-direction.y, direction.x) * (head_size / 2.0);
+
+        path.move_to(back + side);
+        path.line_to(to);
+        path.line_to(back - side);
+    }
+
+    path
+}
+
+/// A point at `radius` from the origin, `angle` radians clockwise from the top.
+fn corner(radius: f64, angle: f64) -> Point {
+    Point::new(angle.sin() * radius, -angle.cos() * radius)
+}
+
+fn closed_path(corners: impl Iterator<Item = Point>) -> BezPath {
+    let corners: Vec<Point> = corners.collect();
+    let mut path = polyline(&corners);
+
+    path.close_path();
+    path
+}
+
+/// Where the first line of a layout goes, its top being at `t` without an anchor.
+fn layout_transform(target: &impl DrawTarget, t: Affine, layout: &TextLayout) -> Affine {
+    match target.anchor() {
+        Some(a) => a.apply(t, layout.bounds),
+        None => t.then_translate(Vec2::new(0.0, layout.size())),
+    }
+}
+
+/// Draws the glyphs and the decorations of a layout. Outlines use `b` everywhere, while fills use the colors of the spans that have one.
+fn draw_layout(scene: &mut Scene, style: &Style, layout: &TextLayout, t: Affine, b: BrushRef) {
+    let brush = |color: Option<Color>| match (style, color) {
+        (Style::Fill(_), Some(c)) => BrushRef::from(c),
+        _ => b.clone(),
+    };
+
+    for run in layout.runs() {
+        let draw = |scene: &mut Scene, style: &Style| {
+            scene
+                .draw_glyphs(&run.font)
+                .font_size(run.font_size)
+                .transform(t)
+                .glyph_transform(None)
+                .normalized_coords(&run.normalized_coords)
+                .brush(brush(run.color))
+                .hint(false)
+                .draw(style, run.glyphs.iter().copied());
+        };
+
+        if !run.synthetic_bold {
+            draw(scene, style);
+            continue;
+        }
+
+        // Thickens the glyphs by a 24th of their size, which is about how much bolder a bold face is.
+        let bolden = run.font_size as f64 / 24.0;
+
+        match style {
+            Style::Fill(_) => {
+                draw(scene, style);
+                draw(scene, &Style::Stroke(Stroke::new(bolden)));
+            }
+            Style::Stroke(s) => draw(
+                scene,
+                &Style::Stroke(Stroke {
+                    width: s.width + bolden,
+                    ..s.clone()
+                }),
+            ),
+        }
+    }
+
+    for d in layout.lines.iter().flat_map(|l| &l.decorations) {
+        match style {
+            Style::Fill(_) => scene.fill(Fill::NonZero, t, brush(d.color), None, &d.rect),
+            Style::Stroke(s) => scene.stroke(s, t, brush(d.color), None, &d.rect),
+        }
+    }
 }
@@ -0,0 +1,178 @@
+//! Brushes with gradients placed relative to the shape they fill.
+
+use vello::kurbo::{Point, Rect};
+use vello::peniko::{self, Color, ColorStop, ColorStops, ColorStopsSource, Extend, Gradient};
+
+/// What a shape or a text is painted with.
+///
+/// The points of the gradients are relative to the bounds of the shape, `(0.0, 0.0)` being its top-left corner and `(1.0, 1.0)` its bottom-right one, so the same brush fits shapes of any size.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Brush {
+    Solid(Color),
+    Linear {
+        start: Point,
+        end: Point,
+        stops: Vec<ColorStop>,
+        extend: Extend,
+    },
+    /// `radius` is relative to half of the biggest side of the bounds, so `1.0` reaches the sides of a square.
+    Radial {
+        center: Point,
+        radius: f32,
+        stops: Vec<ColorStop>,
+        extend: Extend,
+    },
+    /// The angles are in radians, `0.0` pointing right.
+    Sweep {
+        center: Point,
+        start_angle: f32,
+        end_angle: f32,
+        stops: Vec<ColorStop>,
+        extend: Extend,
+    },
+    /// A brush used as-is, in the coordinates of the shape, like an image pattern.
+    Fixed(peniko::Brush),
+}
+
+impl Brush {
+    /// A gradient going from `start` to `end`.
+    pub fn linear(
+        start: impl Into<Point>,
+        end: impl Into<Point>,
+        stops: impl ColorStopsSource,
+    ) -> Self {
+        Self::Linear {
+            start: start.into(),
+            end: end.into(),
+            stops: collect_stops(stops),
+            extend: Extend::Pad,
+        }
+    }
+
+    /// A gradient going from the left side to the right one.
+    pub fn horizontal(stops: impl ColorStopsSource) -> Self {
+        Self::linear((0.0, 0.5), (1.0, 0.5), stops)
+    }
+
+    /// A gradient going from the top to the bottom.
+    pub fn vertical(stops: impl ColorStopsSource) -> Self {
+        Self::linear((0.5, 0.0), (0.5, 1.0), stops)
+    }
+
+    /// A gradient going out of `center`.
+    pub fn radial(center: impl Into<Point>, radius: f32, stops: impl ColorStopsSource) -> Self {
+        Self::Radial {
+            center: center.into(),
+            radius,
+            stops: collect_stops(stops),
+            extend: Extend::Pad,
+        }
+    }
+
+    /// A gradient going around `center`, for a full turn.
+    pub fn sweep(center: impl Into<Point>, stops: impl ColorStopsSource) -> Self {
+        Self::Sweep {
+            center: center.into(),
+            start_angle: 0.0,
+            end_angle: std::f32::consts::TAU,
+            stops: collect_stops(stops),
+            extend: Extend::Pad,
+        }
+    }
+
+    /// Sets what happens past the ends of a gradient: `Pad` keeps the last colors, `Repeat` starts over and `Reflect` goes back and forth.
+    pub fn with_extend(mut self, mode: Extend) -> Self {
+        match &mut self {
+            Self::Linear { extend, .. }
+            | Self::Radial { extend, .. }
+            | Self::Sweep { extend, .. } => {
+                *extend = mode;
+            }
+            Self::Solid(_) | Self::Fixed(_) => {}
+        }
+
+        self
+    }
+
+    /// Whether the brush depends on the bounds of the shape it fills.
+    pub fn is_relative(&self) -> bool {
+        !matches!(self, Self::Solid(_) | Self::Fixed(_))
+    }
+
+    /// Creates the brush to fill a shape with these bounds.
+    pub fn resolve(&self, bounds: Rect) -> peniko::Brush {
+        let at = |p: &Point| {
+            Point::new(
+                bounds.x0 + bounds.width() * p.x,
+                bounds.y0 + bounds.height() * p.y,
+            )
+        };
+
+        match self {
+            Self::Solid(color) => peniko::Brush::Solid(*color),
+            Self::Linear {
+                start,
+                end,
+                stops,
+                extend,
+            } => Gradient::new_linear(at(start), at(end))
+                .with_stops(stops.as_slice())
+                .with_extend(*extend)
+                .into(),
+            Self::Radial {
+                center,
+                radius,
+                stops,
+                extend,
+            } => {
+                let half = bounds.width().max(bounds.height()) as f32 / 2.0;
+
+                Gradient::new_radial(at(center), radius * half)
+                    .with_stops(stops.as_slice())
+                    .with_extend(*extend)
+                    .into()
+            }
+            Self::Sweep {
+                center,
+                start_angle,
+                end_angle,
+                stops,
+                extend,
+            } => Gradient::new_sweep(at(center), *start_angle, *end_angle)
+                .with_stops(stops.as_slice())
+                .with_extend(*extend)
+                .into(),
+            Self::Fixed(brush) => brush.clone(),
+        }
+    }
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self::Solid(Color::WHITE)
+    }
+}
+
+impl From<Color> for Brush {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<peniko::Brush> for Brush {
+    fn from(brush: peniko::Brush) -> Self {
+        Self::Fixed(brush)
+    }
+}
+
+impl From<Gradient> for Brush {
+    fn from(gradient: Gradient) -> Self {
+        Self::Fixed(gradient.into())
+    }
+}
+
+fn collect_stops(source: impl ColorStopsSource) -> Vec<ColorStop> {
+    let mut stops = ColorStops::default();
+    source.collect_stops(&mut stops);
+    stops.to_vec()
+}
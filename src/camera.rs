@@ -0,0 +1,225 @@
+//! Everything related to how your world is seen through the window.
+
+use crate::instance::View;
+use crate::prelude::*;
+use kurbo::{Affine, Rect, Vec2};
+
+/// The area of the window a [`Camera2d`] draws into.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Viewport {
+    /// A rectangle in fractions of the window, where `(0.0, 0.0, 1.0, 1.0)` is the whole window.
+    Relative(Rect),
+    /// A rectangle in window pixels.
+    Pixels(Rect),
+}
+
+impl Viewport {
+    /// Converts the viewport to window pixels.
+    pub fn to_pixels(&self, resolution: Vec2) -> Rect {
+        match self {
+            Viewport::Relative(r) => Rect::new(
+                r.x0 * resolution.x,
+                r.y0 * resolution.y,
+                r.x1 * resolution.x,
+                r.y1 * resolution.y,
+            ),
+            Viewport::Pixels(r) => *r,
+        }
+    }
+}
+
+/// A 2D camera. Every active [`Camera2d`] of a world pans, rotates and zooms the scenes of its [`Instance`] into its viewport, except the ones set as screen space with [`Instance::set_screen_space`].
+///
+/// - `position` is the point of the world shown at the center of the viewport.
+/// - `rotation` is the rotation of the camera, in radians.
+/// - `zoom` scales the world, `2.0` makes everything look twice as big.
+/// - `follow` is an entity with a [`GlobalTransform`] that the camera will stay centered on.
+/// - `bounds` is an area of the world the camera can't look outside of.
+/// - `viewport` is the area of the window the camera draws into, `None` being the whole window.
+/// - `scenes` are the names of the scenes this camera shows, `None` showing all of them.
+/// - `order` decides which camera is drawn on top when viewports overlap, the highest being on top.
+#[derive(Component, Debug, Clone)]
+pub struct Camera2d {
+    pub position: Vec2,
+    pub rotation: f64,
+    pub zoom: f64,
+    pub follow: Option<Entity>,
+    pub bounds: Option<Rect>,
+    pub viewport: Option<Viewport>,
+    pub scenes: Option<Vec<String>>,
+    pub order: isize,
+    pub active: bool,
+
+    view: Affine,
+    area: Rect,
+}
+
+impl Default for Camera2d {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            rotation: 0.0,
+            zoom: 1.0,
+            follow: None,
+            bounds: None,
+            viewport: None,
+            scenes: None,
+            order: 0,
+            active: true,
+            view: Affine::IDENTITY,
+            area: Rect::ZERO,
+        }
+    }
+}
+
+impl Camera2d {
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_zoom(mut self, zoom: f64) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: f64) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_follow(mut self, target: Entity) -> Self {
+        self.follow = Some(target);
+        self
+    }
+
+    pub fn with_bounds(mut self, bounds: Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    pub fn with_viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    pub fn with_scenes(mut self, scenes: &[&str]) -> Self {
+        self.scenes = Some(scenes.iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    pub fn with_order(mut self, order: isize) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// The transformation that goes from world space to screen space, as of the last frame.
+    pub fn view(&self) -> Affine {
+        self.view
+    }
+
+    /// Converts a point of the window (like [`Input::mouse_position`]) to a point of the world.
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        (self.view.inverse() * point.to_point()).to_vec2()
+    }
+
+    /// Converts a point of the world to a point of the window.
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        (self.view * point.to_point()).to_vec2()
+    }
+
+    /// The area of the window this camera draws into, in pixels, as of the last frame.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// Checks if a point of the window (like [`Input::mouse_position`]) is inside of the viewport of this camera.
+    pub fn contains_screen_point(&self, point: Vec2) -> bool {
+        self.area.contains(point.to_point())
+    }
+
+    /// Calculates the area of the window this camera draws into, for a window of the given resolution.
+    pub fn area_for(&self, resolution: Vec2) -> Rect {
+        match &self.viewport {
+            Some(v) => v.to_pixels(resolution),
+            None => Rect::from_origin_size((0.0, 0.0), resolution.to_size()),
+        }
+    }
+
+    /// Calculates the view of the camera for a window of the given resolution.
+    pub fn view_for(&self, resolution: Vec2) -> Affine {
+        Affine::translate(self.area_for(resolution).center().to_vec2())
+            * Affine::scale(self.zoom)
+            * Affine::rotate(-self.rotation)
+            * Affine::translate(-self.position)
+    }
+
+    /// Keeps `position` inside of `bounds`, taking into account how much of the world fits in the viewport.
+    fn clamp_to_bounds(&mut self) {
+        let Some(bounds) = self.bounds else {
+            return;
+        };
+
+        let half = self.area.size().to_vec2() / (2.0 * self.zoom);
+
+        self.position.x = if bounds.width() <= half.x * 2.0 {
+            bounds.center().x
+        } else {
+            self.position
+                .x
+                .clamp(bounds.x0 + half.x, bounds.x1 - half.x)
+        };
+
+        self.position.y = if bounds.height() <= half.y * 2.0 {
+            bounds.center().y
+        } else {
+            self.position
+                .y
+                .clamp(bounds.y0 + half.y, bounds.y1 - half.y)
+        };
+    }
+}
+
+/// Moves the cameras towards their targets and updates the views of the [`Instance`].
+pub fn camera_system(
+    mut cameras: Query<&mut Camera2d>,
+    transforms: Query<&GlobalTransform>,
+    mut instance: ResMut<Instance>,
+) {
+    let resolution = *instance.resolution();
+    let mut views = vec![];
+
+    for mut camera in &mut cameras {
+        if let Some(target) = camera.follow {
+            if let Ok(t) = transforms.get(target) {
+                camera.position = t.affine.translation();
+            }
+        }
+
+        camera.area = camera.area_for(resolution);
+        camera.clamp_to_bounds();
+        camera.view = camera.view_for(resolution);
+
+        if camera.active {
+            let scenes = camera
+                .scenes
+                .as_ref()
+                .map(|names| names.iter().filter_map(|n| instance.scene_id(n)).collect());
+
+            views.push((
+                camera.order,
+                View {
+                    transform: camera.view,
+                    clip: camera.viewport.map(|_| camera.area),
+                    scenes,
+                },
+            ));
+        }
+    }
+
+    views.sort_by_key(|(order, _)| *order);
+
+    instance.set_views(views.into_iter().map(|(_, v)| v).collect());
+}
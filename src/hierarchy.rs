@@ -0,0 +1,157 @@
+//! Parent and children relationships between entities.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::{QueryData, QueryFilter, ROQueryItem};
+use bevy_ecs::system::EntityCommands;
+
+/// The entity this entity is attached to. Its [`Transform`](crate::transforms::Transform) becomes relative to the one of its parent.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parent(pub Entity);
+
+/// The entities attached to this entity.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Children(pub Vec<Entity>);
+
+/// Spawns the children of an entity, returned by [`BuildChildren::with_children`].
+pub struct ChildBuilder<'a> {
+    commands: Commands<'a, 'a>,
+    parent: Entity,
+    children: Vec<Entity>,
+}
+
+impl ChildBuilder<'_> {
+    /// Spawns a new entity as a child.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> EntityCommands<'_> {
+        let child = self.commands.spawn((bundle, Parent(self.parent))).id();
+        self.children.push(child);
+        self.commands.entity(child)
+    }
+
+    pub fn parent_entity(&self) -> Entity {
+        self.parent
+    }
+}
+
+/// Extends [`EntityCommands`] with ways to attach children to an entity.
+pub trait BuildChildren {
+    /// Spawns children attached to this entity.
+    fn with_children(&mut self, f: impl FnOnce(&mut ChildBuilder)) -> &mut Self;
+
+    /// Attaches an already existing entity as a child of this one.
+    fn add_child(&mut self, child: Entity) -> &mut Self;
+}
+
+impl BuildChildren for EntityCommands<'_> {
+    fn with_children(&mut self, f: impl FnOnce(&mut ChildBuilder)) -> &mut Self {
+        let parent = self.id();
+
+        let mut builder = ChildBuilder {
+            commands: self.commands(),
+            parent,
+            children: vec![],
+        };
+
+        f(&mut builder);
+
+        let children = builder.children;
+        self.add(move |entity: Entity, world: &mut World| {
+            push_children(world, entity, &children);
+        })
+    }
+
+    fn add_child(&mut self, child: Entity) -> &mut Self {
+        self.add(move |entity: Entity, world: &mut World| {
+            detach_from_parent(world, child);
+
+            if let Some(mut c) = world.get_entity_mut(child) {
+                c.insert(Parent(entity));
+            }
+
+            push_children(world, entity, &[child]);
+        })
+    }
+}
+
+/// Extends [`EntityCommands`] with a way to despawn an entity along with all of its children.
+pub trait DespawnRecursiveExt {
+    /// Despawns this entity, its children, the children of its children and so on.
+    fn despawn_recursive(self);
+}
+
+impl DespawnRecursiveExt for EntityCommands<'_> {
+    fn despawn_recursive(mut self) {
+        self.add(|entity: Entity, world: &mut World| {
+            detach_from_parent(world, entity);
+            despawn_with_children(world, entity);
+        });
+    }
+}
+
+/// Filters the entities at the top of the hierarchy that have a `C` component or [`Children`], which is where [`propagate_hierarchy`] starts from.
+pub type HierarchyRoot<C> = (Without<Parent>, Or<(With<C>, With<Children>)>);
+
+/// Walks down the hierarchy from each of the `roots` to all of their children, the children of those and so on, reading the data of every entity from `nodes`.
+///
+/// `f` gets each entity, its data and the value returned for its parent (`root` for the roots), and returns the value passed on to its children.
+/// Used to propagate things like transforms and visibility.
+pub fn propagate_hierarchy<D: QueryData, F: QueryFilter, T: Copy>(
+    roots: impl IntoIterator<Item = Entity>,
+    nodes: &Query<(D, Option<&Children>), F>,
+    root: T,
+    mut f: impl FnMut(Entity, ROQueryItem<'_, D>, T) -> T,
+) {
+    for entity in roots {
+        propagate_node(nodes, entity, root, &mut f);
+    }
+}
+
+fn propagate_node<D: QueryData, F: QueryFilter, T: Copy>(
+    nodes: &Query<(D, Option<&Children>), F>,
+    entity: Entity,
+    parent: T,
+    f: &mut impl FnMut(Entity, ROQueryItem<'_, D>, T) -> T,
+) {
+    let Ok((data, children)) = nodes.get(entity) else {
+        return;
+    };
+
+    let value = f(entity, data, parent);
+
+    for child in children.into_iter().flat_map(|c| &c.0) {
+        propagate_node(nodes, *child, value, f);
+    }
+}
+
+fn push_children(world: &mut World, parent: Entity, children: &[Entity]) {
+    let Some(mut p) = world.get_entity_mut(parent) else {
+        return;
+    };
+
+    match p.get_mut::<Children>() {
+        Some(mut c) => c.0.extend_from_slice(children),
+        None => {
+            p.insert(Children(children.to_vec()));
+        }
+    }
+}
+
+/// Removes an entity from the [`Children`] of its current parent, if it has one.
+fn detach_from_parent(world: &mut World, entity: Entity) {
+    let Some(Parent(parent)) = world.get::<Parent>(entity).copied() else {
+        return;
+    };
+
+    if let Some(mut c) = world.get_mut::<Children>(parent) {
+        c.0.retain(|e| *e != entity);
+    }
+}
+
+fn despawn_with_children(world: &mut World, entity: Entity) {
+    if let Some(children) = world.get::<Children>(entity) {
+        for child in children.0.clone() {
+            despawn_with_children(world, child);
+        }
+    }
+
+    world.despawn(entity);
+}
@@ -19,6 +19,7 @@ use crossbeam_queue::SegQueue;
 /// - `key_down` is a vector that contains all of the "key down"'s derected by [`recieve_inputs`].
 /// - `key_up` is a vector that contains all of the "key up"'s derected by [`recieve_inputs`].
 /// - `key_press` is a vector that contains all of the keys currently being pressed, derected by [`recieve_inputs`].
+/// - `key_just_pressed` is a vector that contains the keys that started being pressed this frame, without the repeats of the keys that are held.
 #[derive(Resource, Default)]
 pub struct Input {
     key_down_queue: SegQueue<u32>,
@@ -31,6 +32,7 @@ pub struct Input {
     key_down: Vec<u32>,
     key_up: Vec<u32>,
     key_press: Vec<u32>,
+    key_just_pressed: Vec<u32>,
 
     mouse_pos: kurbo::Vec2,
     mouse_down: Vec<MouseButton>,
@@ -42,6 +44,7 @@ pub struct Input {
 pub fn recieve_inputs(mut input: ResMut<Input>) {
     input.key_down.clear();
     input.key_up.clear();
+    input.key_just_pressed.clear();
     input.mouse_down.clear();
     input.mouse_up.clear();
 
@@ -58,6 +61,7 @@ pub fn recieve_inputs(mut input: ResMut<Input>) {
 
         if !is_key_already_pressed {
             input.key_press.push(k);
+            input.key_just_pressed.push(k);
         }
     }
 
@@ -137,6 +141,17 @@ impl Input {
         false
     }
 
+    /// Checks if a key started being pressed this frame. Unlike [`Input::is_key_down`], it's not repeated while the key is held.
+    pub fn is_key_just_pressed(&self, key: KeyCode) -> bool {
+        for k in &self.key_just_pressed {
+            if get_keycode_from_physical_key(KeyCode::from_scancode(*k)) == key {
+                return true;
+            }
+        }
+
+        false
+    }
+
     /// Checks if a key is up.
     pub fn is_key_up(&self, key: KeyCode) -> bool {
         for k in &self.key_up {
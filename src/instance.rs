@@ -1,33 +1,293 @@
 use crate::prelude::*;
-use kurbo::Vec2;
+use kurbo::{Affine, Rect, Vec2};
 use std::collections::HashMap;
+use vello::peniko::{BlendMode, Compose, Mix};
 use vello::Scene;
 
+/// A handle to a scene of an [`Instance`], returned when it's created.
+///
+/// Accessing a scene through its [`SceneId`] doesn't need to look up its name, so prefer it in systems that run every frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SceneId(usize);
+
+/// Anything that can point to a scene of an [`Instance`]: its name or its [`SceneId`].
+pub trait SceneKey {
+    fn scene_id(&self, instance: &Instance) -> Option<SceneId>;
+}
+
+impl SceneKey for SceneId {
+    fn scene_id(&self, instance: &Instance) -> Option<SceneId> {
+        instance.scenes.contains_key(self).then_some(*self)
+    }
+}
+
+impl SceneKey for &str {
+    fn scene_id(&self, instance: &Instance) -> Option<SceneId> {
+        instance.scene_names.get(*self).copied()
+    }
+}
+
+impl SceneKey for String {
+    fn scene_id(&self, instance: &Instance) -> Option<SceneId> {
+        instance.scene_names.get(self).copied()
+    }
+}
+
+impl SceneKey for &String {
+    fn scene_id(&self, instance: &Instance) -> Option<SceneId> {
+        instance.scene_names.get(*self).copied()
+    }
+}
+
+/// How a scene is composed into the window.
+///
+/// - `layer` decides the order scenes are drawn in, the highest being on top. Scenes on the same layer are drawn in the order they were created.
+/// - `visible` hides the scene when `false`.
+/// - `opacity` goes from `0.0` (invisible) to `1.0` (opaque).
+/// - `blend` is how the scene is blended with the scenes below it.
+/// - `screen_space` draws the scene in window pixels, ignoring every [`Camera2d`]. Useful for UI and HUDs. These are always drawn on top of the cameras.
+/// - `persistent` keeps the scene from being reset every frame, so it only has to be encoded again after [`Instance::mark_scene_dirty`].
+#[derive(Debug, Clone, Copy)]
+pub struct SceneProperties {
+    pub layer: isize,
+    pub visible: bool,
+    pub opacity: f32,
+    pub blend: BlendMode,
+    pub screen_space: bool,
+    pub persistent: bool,
+
+    // Set when the scene has to be encoded again on the next frame.
+    dirty: bool,
+    // Set when the scene was reset this frame and has to be encoded.
+    redraw: bool,
+}
+
+impl Default for SceneProperties {
+    fn default() -> Self {
+        Self {
+            layer: 0,
+            visible: true,
+            opacity: 1.0,
+            blend: BlendMode::new(Mix::Normal, Compose::SrcOver),
+            screen_space: false,
+            persistent: false,
+            dirty: true,
+            redraw: true,
+        }
+    }
+}
+
+impl SceneProperties {
+    /// Checks if the scene needs its own layer to be composed.
+    fn needs_layer(&self) -> bool {
+        self.opacity < 1.0 || self.blend != BlendMode::new(Mix::Normal, Compose::SrcOver)
+    }
+}
+
+/// How the scenes of an [`Instance`] are seen through a [`Camera2d`].
+///
+/// - `transform` goes from world space to window pixels.
+/// - `clip` is the area of the window the view draws into, `None` being the whole window.
+/// - `scenes` are the ids of the scenes shown, `None` showing all of them.
+#[derive(Debug, Clone)]
+pub struct View {
+    pub transform: Affine,
+    pub clip: Option<Rect>,
+    pub scenes: Option<Vec<SceneId>>,
+}
+
+impl View {
+    pub fn shows(&self, id: SceneId) -> bool {
+        match &self.scenes {
+            Some(s) => s.contains(&id),
+            None => true,
+        }
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct Instance {
-    pub max_scene_id: usize,
-    pub scenes: HashMap<usize, Scene>,
-    pub scene_names: HashMap<String, usize>,
+    max_scene_id: usize,
+    scenes: HashMap<SceneId, Scene>,
+    scene_names: HashMap<String, SceneId>,
+    names_by_id: HashMap<SceneId, String>,
     asset_server: AssetServer,
     resolution: Vec2,
+    views: Vec<View>,
+    properties: HashMap<SceneId, SceneProperties>,
 }
 
 impl Instance {
-    pub fn new_scene(&mut self, name: &str) -> Option<&mut Scene> {
+    /// Creates a new scene. If there's already a scene with the same name, it gets replaced.
+    pub fn new_scene(&mut self, name: &str) -> SceneId {
+        self.new_scene_with_layer(name, 0)
+    }
+
+    /// Creates a new scene, drawn on the given layer. If there's already a scene with the same name, it gets replaced.
+    pub fn new_scene_with_layer(&mut self, name: &str, layer: isize) -> SceneId {
+        self.remove_scene(name);
+
         self.max_scene_id += 1;
-        self.scenes.insert(self.max_scene_id, Scene::new());
-        self.scene_names.insert(name.to_string(), self.max_scene_id);
+        let id = SceneId(self.max_scene_id);
+
+        self.scenes.insert(id, Scene::new());
+        self.scene_names.insert(name.to_string(), id);
+        self.names_by_id.insert(id, name.to_string());
+        self.properties.insert(
+            id,
+            SceneProperties {
+                layer,
+                ..Default::default()
+            },
+        );
+
+        id
+    }
+
+    /// Gets the scene with the given name, or creates it if it doesn't exist.
+    pub fn get_or_new_scene(&mut self, name: &str) -> SceneId {
+        match name.scene_id(self) {
+            Some(id) => id,
+            None => self.new_scene(name),
+        }
+    }
+
+    pub fn get_scene(&mut self, key: impl SceneKey) -> Option<&mut Scene> {
+        let id = key.scene_id(self)?;
+        self.scenes.get_mut(&id)
+    }
+
+    pub fn has_scene(&self, key: impl SceneKey) -> bool {
+        key.scene_id(self).is_some()
+    }
+
+    /// Gets the [`SceneId`] of a scene by its name.
+    pub fn scene_id(&self, name: &str) -> Option<SceneId> {
+        name.scene_id(self)
+    }
+
+    pub fn scene_name(&self, id: SceneId) -> Option<&str> {
+        self.names_by_id.get(&id).map(|n| n.as_str())
+    }
 
-        self.scenes.get_mut(&self.max_scene_id)
+    /// Iterates over the names of all of the scenes and their ids, in the order they were created.
+    pub fn scene_names(&self) -> impl Iterator<Item = (&str, SceneId)> {
+        let mut names: Vec<(&str, SceneId)> = self
+            .names_by_id
+            .iter()
+            .map(|(i, n)| (n.as_str(), *i))
+            .collect();
+
+        names.sort_by_key(|(_, i)| *i);
+        names.into_iter()
+    }
+
+    /// Iterates over all of the scenes and their ids, in the order they were created.
+    pub fn scenes(&self) -> impl Iterator<Item = (SceneId, &Scene)> {
+        let mut scenes: Vec<(SceneId, &Scene)> = self.scenes.iter().map(|(i, s)| (*i, s)).collect();
+
+        scenes.sort_by_key(|(i, _)| *i);
+        scenes.into_iter()
+    }
+
+    /// Removes a scene, returning it if it existed.
+    pub fn remove_scene(&mut self, key: impl SceneKey) -> Option<Scene> {
+        let id = key.scene_id(self)?;
+
+        if let Some(name) = self.names_by_id.remove(&id) {
+            self.scene_names.remove(&name);
+        }
+
+        self.properties.remove(&id);
+        self.scenes.remove(&id)
     }
 
-    pub fn get_scene(&mut self, name: &str) -> Option<&mut Scene> {
-        let ptr = self.scene_names.get(name);
+    /// Changes the name of a scene, keeping its [`SceneId`]. Returns `false` if the scene doesn't exist or the new name is already taken.
+    pub fn rename_scene(&mut self, key: impl SceneKey, name: &str) -> bool {
+        let Some(id) = key.scene_id(self) else {
+            return false;
+        };
+
+        if self.scene_names.contains_key(name) {
+            return false;
+        }
 
-        match ptr {
-            Some(p) => self.scenes.get_mut(p),
-            None => None,
+        if let Some(old) = self.names_by_id.insert(id, name.to_string()) {
+            self.scene_names.remove(&old);
         }
+
+        self.scene_names.insert(name.to_string(), id);
+        true
+    }
+
+    pub fn scene_properties(&self, key: impl SceneKey) -> Option<&SceneProperties> {
+        let id = key.scene_id(self)?;
+        self.properties.get(&id)
+    }
+
+    pub fn scene_properties_mut(&mut self, key: impl SceneKey) -> Option<&mut SceneProperties> {
+        let id = key.scene_id(self)?;
+        self.properties.get_mut(&id)
+    }
+
+    pub fn set_scene_layer(&mut self, key: impl SceneKey, layer: isize) {
+        if let Some(p) = self.scene_properties_mut(key) {
+            p.layer = layer;
+        }
+    }
+
+    pub fn set_scene_visible(&mut self, key: impl SceneKey, visible: bool) {
+        if let Some(p) = self.scene_properties_mut(key) {
+            p.visible = visible;
+        }
+    }
+
+    pub fn set_scene_opacity(&mut self, key: impl SceneKey, opacity: f32) {
+        if let Some(p) = self.scene_properties_mut(key) {
+            p.opacity = opacity.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn set_scene_blend(&mut self, key: impl SceneKey, blend: impl Into<BlendMode>) {
+        if let Some(p) = self.scene_properties_mut(key) {
+            p.blend = blend.into();
+        }
+    }
+
+    /// Sets if a scene is drawn in window pixels, ignoring the [`Camera2d`]. Useful for UI and HUDs.
+    pub fn set_screen_space(&mut self, key: impl SceneKey, screen_space: bool) {
+        if let Some(p) = self.scene_properties_mut(key) {
+            p.screen_space = screen_space;
+        }
+    }
+
+    /// Sets if a scene keeps its encoding between frames instead of being reset. It gets encoded again only after [`Instance::mark_scene_dirty`].
+    pub fn set_scene_persistent(&mut self, key: impl SceneKey, persistent: bool) {
+        if let Some(p) = self.scene_properties_mut(key) {
+            p.persistent = persistent;
+            p.dirty = true;
+        }
+    }
+
+    /// Marks a persistent scene to be reset and encoded again on the next frame, when [`Instance::needs_redraw`] returns `true` for it.
+    pub fn mark_scene_dirty(&mut self, key: impl SceneKey) {
+        if let Some(p) = self.scene_properties_mut(key) {
+            p.dirty = true;
+        }
+    }
+
+    /// Checks if a scene was reset this frame and has to be encoded. This is always `true` for scenes that aren't persistent.
+    pub fn needs_redraw(&self, key: impl SceneKey) -> bool {
+        self.scene_properties(key).is_some_and(|p| p.redraw)
+    }
+
+    pub fn is_screen_space(&self, id: SceneId) -> bool {
+        self.properties_of(id).screen_space
+    }
+
+    /// The properties of a scene by its id, or the default ones if it has none.
+    fn properties_of(&self, id: SceneId) -> SceneProperties {
+        self.properties.get(&id).copied().unwrap_or_default()
     }
 
     pub fn asset_server(&mut self) -> &mut AssetServer {
@@ -41,11 +301,120 @@ impl Instance {
     pub fn set_resolution(&mut self, x: u32, y: u32) {
         self.resolution = Vec2::new(x as f64, y as f64);
     }
+
+    /// The views every scene that isn't in screen space is drawn through. Set by the active [`Camera2d`]s, in the order they're drawn.
+    pub fn views(&self) -> &[View] {
+        &self.views
+    }
+
+    pub fn set_views(&mut self, views: Vec<View>) {
+        self.views = views;
+    }
+
+    /// Checks if a rectangle of a scene, in world space, can be seen through any of the views (or the window, for screen space scenes).
+    pub fn is_rect_visible(&self, id: SceneId, rect: Rect) -> bool {
+        if self.resolution == Vec2::ZERO {
+            return true;
+        }
+
+        let window = Rect::from_origin_size((0.0, 0.0), self.resolution.to_size());
+
+        if self.is_screen_space(id) || self.views.is_empty() {
+            return overlaps(rect, window);
+        }
+
+        self.views.iter().filter(|v| v.shows(id)).any(|v| {
+            let area = v.clip.unwrap_or(window);
+            overlaps(rect, v.transform.inverse().transform_rect_bbox(area))
+        })
+    }
+
+    /// The ids of the visible scenes, in the order they're drawn.
+    pub fn ordered_scenes(&self) -> Vec<SceneId> {
+        let mut ids: Vec<SceneId> = self
+            .scenes
+            .keys()
+            .copied()
+            .filter(|id| self.properties_of(*id).visible)
+            .collect();
+
+        ids.sort_by_key(|id| (self.properties_of(*id).layer, *id));
+        ids
+    }
+
+    /// Appends all of the scenes into `main`, through every view, followed by the scenes in screen space.
+    pub fn compose(&self, main: &mut Scene) {
+        let ordered = self.ordered_scenes();
+        let window = Rect::from_origin_size((0.0, 0.0), self.resolution.to_size());
+
+        if self.views.is_empty() {
+            for id in ordered.iter().filter(|id| !self.is_screen_space(**id)) {
+                self.append_scene(main, *id, None, window);
+            }
+        }
+
+        for view in &self.views {
+            let area = view.clip.unwrap_or(window);
+
+            if view.clip.is_some() {
+                main.push_layer(Mix::Clip, 1.0, Affine::IDENTITY, &area);
+            }
+
+            for id in ordered.iter().filter(|id| !self.is_screen_space(**id)) {
+                if view.shows(*id) {
+                    self.append_scene(main, *id, Some(view.transform), area);
+                }
+            }
+
+            if view.clip.is_some() {
+                main.pop_layer();
+            }
+        }
+
+        for id in ordered.iter().filter(|id| self.is_screen_space(**id)) {
+            self.append_scene(main, *id, None, window);
+        }
+    }
+
+    /// Appends a single scene into `main`, wrapping it in a layer if it has an opacity or blend mode.
+    fn append_scene(&self, main: &mut Scene, id: SceneId, transform: Option<Affine>, area: Rect) {
+        let Some(scene) = self.scenes.get(&id) else {
+            return;
+        };
+
+        let properties = self.properties_of(id);
+
+        if properties.needs_layer() {
+            main.push_layer(
+                properties.blend,
+                properties.opacity,
+                Affine::IDENTITY,
+                &area,
+            );
+            main.append(scene, transform);
+            main.pop_layer();
+        } else {
+            main.append(scene, transform);
+        }
+    }
+}
+
+fn overlaps(a: Rect, b: Rect) -> bool {
+    a.x0 <= b.x1 && a.x1 >= b.x0 && a.y0 <= b.y1 && a.y1 >= b.y0
 }
 
+/// Resets every scene at the start of the frame, except the persistent ones that aren't dirty.
 pub fn bella_instance_reset(mut root: ResMut<Instance>) {
-    #[allow(clippy::for_kv_map)]
-    for (_id, scene) in &mut root.scenes {
-        scene.reset();
+    let root = &mut *root;
+
+    for (id, scene) in &mut root.scenes {
+        let properties = root.properties.entry(*id).or_default();
+
+        properties.redraw = !properties.persistent || properties.dirty;
+        properties.dirty = false;
+
+        if properties.redraw {
+            scene.reset();
+        }
     }
 }
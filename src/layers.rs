@@ -0,0 +1,70 @@
+//! Clipping, opacity and blending for an entity and all of its children.
+//!
+//! Any of [`ClipShape`], [`Opacity`] and [`Blend`] makes [`draw_shapes`] wrap the drawing of the entity and its children (the ones drawn into the same scene) in a single layer.
+//!
+//! The entity with the layer needs a [`SceneTarget`] (and a [`Transform`]) itself, even if it draws nothing: that's the scene its layer goes into. Otherwise, its children are drawn without it.
+//!
+//! Layers without a [`ClipShape`] still clip to a square of `±1e6` around the origin of their scene, because vello's layers always have a clip. What's drawn farther away is cut, which only shows with huge coordinates or a camera zoomed far out.
+
+use crate::prelude::*;
+use kurbo::{Affine, BezPath, Rect, Shape};
+use peniko::{BlendMode, Mix};
+use vello::Scene;
+
+/// The area used by layers without a [`ClipShape`], in the coordinates of the scene. It's big enough for usual scenes, but it still cuts what's farther away.
+const UNCLIPPED: Rect = Rect::new(-1e6, -1e6, 1e6, 1e6);
+
+/// Hides everything drawn by the entity and its children outside of a shape, relative to the entity.
+#[derive(Component, Debug, Clone)]
+pub struct ClipShape(pub BezPath);
+
+impl ClipShape {
+    pub fn from_shape(shape: &impl Shape) -> Self {
+        Self(shape.to_path(0.1))
+    }
+}
+
+/// Draws the entity and its children as a group with this opacity, from `0.0` to `1.0`.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Opacity(pub f32);
+
+/// Blends the entity and its children with what's under them.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Blend(pub BlendMode);
+
+impl Blend {
+    pub fn new(blend: impl Into<BlendMode>) -> Self {
+        Self(blend.into())
+    }
+}
+
+impl Default for Blend {
+    fn default() -> Self {
+        Self(Mix::Normal.into())
+    }
+}
+
+/// Pushes the layer of an entity into a scene, if it has any of the layer components.
+///
+/// Returns whether a layer was pushed, which must then be popped with `pop_layer`.
+pub(crate) fn push_entity_layer(
+    scene: &mut Scene,
+    t: Affine,
+    clip: Option<&ClipShape>,
+    opacity: Option<&Opacity>,
+    blend: Option<&Blend>,
+) -> bool {
+    if clip.is_none() && opacity.is_none() && blend.is_none() {
+        return false;
+    }
+
+    let blend = blend.copied().unwrap_or_default().0;
+    let alpha = opacity.map_or(1.0, |o| o.0.clamp(0.0, 1.0));
+
+    match clip {
+        Some(c) => scene.push_layer(blend, alpha, t, &c.0),
+        None => scene.push_layer(blend, alpha, Affine::IDENTITY, &UNCLIPPED),
+    }
+
+    true
+}
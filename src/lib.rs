@@ -6,10 +6,22 @@
 
 pub mod assets;
 pub mod basics;
+pub mod brush;
+pub mod camera;
+pub mod hierarchy;
 pub mod input;
 pub mod instance;
+pub mod layers;
+pub mod rich_text;
+pub mod scene_writer;
+pub mod shapes;
+pub mod sprites;
+pub mod text;
 pub mod time;
 pub mod transforms;
+pub mod trim;
+pub mod visibility;
+pub mod window;
 
 pub extern crate interpoli;
 
@@ -25,6 +37,7 @@ pub mod prelude {
         window::Window,
     };
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
     #[doc(hidden)]
@@ -48,14 +61,40 @@ pub mod prelude {
 
     #[doc(hidden)]
     pub use crate::{
-        assets::{AssetServer, Format, ToFontRef},
-        basics::SceneBasics,
+        assets::{AssetServer, FontAxis, FontError, FontFace, FontInfo, Format, ToFontRef},
+        basics::{Anchor, Anchored, DrawTarget, SceneBasics, Trimmed},
+        brush::Brush,
+        camera::{camera_system, Camera2d, Viewport},
+        hierarchy::{BuildChildren, ChildBuilder, Children, DespawnRecursiveExt, Parent},
         input::{recieve_inputs, Input, MouseButton},
-        instance::{bella_instance_reset, Instance},
+        instance::{bella_instance_reset, Instance, SceneId, SceneKey, SceneProperties},
+        layers::{Blend, ClipShape, Opacity},
+        rich_text::{MarkupError, RichText, TextSpan, TextStyle},
+        scene_writer::{merge_scene_fragments, SceneFragments, SceneWriter},
+        shapes::{
+            draw_shapes, FillStyle, SceneTarget, Shadow, ShapeCircle, ShapePath, ShapeRoundedRect,
+            StrokeStyle, TextLabel, TextLabelLayout, TextOutline,
+        },
+        sprites::VectorSprite,
+        text::{
+            measure_text, GlyphRun, TextAlign, TextDecoration, TextLayout, TextLine, TextMetrics,
+            VerticalAlign,
+        },
         time::{time_system, Real, Time, Virtual},
-        transforms::Transform,
+        transforms::{
+            propagate_transforms, GlobalTransform, Transform, TransformComponents, ZIndex,
+            ZSortedQuery,
+        },
+        trim::{trim_path, Trim},
+        visibility::{
+            compute_bounds, propagate_visibility, Aabb, CullingStats, InheritedVisibility,
+            Visibility,
+        },
+        window::Windows,
     };
 
+    use crate::window::WindowCommand;
+
     #[doc(hidden)]
     pub use bevy_ecs::prelude::*;
 
@@ -99,6 +138,82 @@ pub mod prelude {
         Suspended(Option<Arc<Window>>),
     }
 
+    /// A window of your [`App`], with its own surface and the scene its worlds are composed into.
+    struct BellaWindow<'s> {
+        id: usize,
+
+        title: String,
+        width: u32,
+        height: u32,
+
+        new_resize: bool,
+        is_resizing: bool,
+
+        state: RenderState<'s>,
+        main_scene: Scene,
+    }
+
+    impl BellaWindow<'_> {
+        fn new(id: usize, title: &str, width: u32, height: u32) -> Self {
+            Self {
+                id,
+
+                title: title.to_string(),
+                width,
+                height,
+
+                new_resize: false,
+                is_resizing: false,
+
+                state: RenderState::Suspended(None),
+                main_scene: Scene::new(),
+            }
+        }
+
+        fn is_winit_window(&self, window_id: winit::window::WindowId) -> bool {
+            matches!(&self.state, RenderState::Active(state) if state.window.id() == window_id)
+        }
+    }
+
+    /// Creates (or reuses) the winit window of a [`BellaWindow`] and its vello surface.
+    fn activate_window(
+        event_loop: &ActiveEventLoop,
+        context: &mut RenderContext,
+        renderers: &mut Vec<Option<Renderer>>,
+        bella_window: &mut BellaWindow<'_>,
+    ) {
+        let RenderState::Suspended(cached_window) = &mut bella_window.state else {
+            return;
+        };
+
+        // Get the winit window cached in a previous Suspended event or else create a new window
+        let window = cached_window.take().unwrap_or_else(|| {
+            create_winit_window(
+                event_loop,
+                &bella_window.title,
+                bella_window.width,
+                bella_window.height,
+            )
+        });
+
+        // Create a vello Surface
+        let size = window.inner_size();
+        let surface_future = context.create_surface(
+            window.clone(),
+            size.width,
+            size.height,
+            wgpu::PresentMode::AutoVsync,
+        );
+        let surface = pollster::block_on(surface_future).expect("Error creating surface");
+
+        // Create a vello Renderer for the surface (using its device id)
+        renderers.resize_with(context.devices.len(), || None);
+        renderers[surface.dev_id].get_or_insert_with(|| create_vello_renderer(context, &surface));
+
+        // Save the Window and Surface to a state variable
+        bella_window.state = RenderState::Active(ActiveRenderState { window, surface });
+    }
+
     pub struct BellaWorld {
         pub main: World,
 
@@ -109,6 +224,12 @@ pub mod prelude {
         pub sch_on_update: Schedule,
         pub sch_on_last: Schedule,
 
+        /// The id of the window this world is drawn into, `None` once its window is closed. It's neither updated nor drawn until it's assigned to another window.
+        pub window: Option<usize>,
+
+        /// Merges what was drawn with [`SceneWriter`]s, once every `on_draw` system is done.
+        sch_after_draw: Schedule,
+
         on_start: bool,
     }
 
@@ -127,13 +248,27 @@ pub mod prelude {
             world.insert_resource(Time::new_with(Virtual::default()));
             world.insert_resource(Time::new_with(Real::default()));
             world.insert_resource(Input::default());
+            world.insert_resource(Windows::default());
+            world.insert_resource(CullingStats::default());
+            world.insert_resource(SceneFragments::default());
 
             let mut sch_on_first = Schedule::default();
 
             sch_on_first.add_systems(time_system);
             sch_on_first.add_systems(bella_instance_reset);
+            sch_on_first.add_systems(propagate_transforms);
+            sch_on_first.add_systems(camera_system.after(propagate_transforms));
+            sch_on_first.add_systems(propagate_visibility);
+            sch_on_first.add_systems(compute_bounds);
+
+            let mut sch_on_draw = Schedule::default();
+
+            // The systems added with `on_draw` run after it, see `App::on_draw`.
+            sch_on_draw.add_systems(draw_shapes);
+
+            let mut sch_after_draw = Schedule::default();
+            sch_after_draw.add_systems(merge_scene_fragments);
 
-            let sch_on_draw = Schedule::default();
             let mut sch_on_pre_update = Schedule::default();
 
             sch_on_pre_update.add_systems(recieve_inputs);
@@ -146,6 +281,8 @@ pub mod prelude {
                 sch_on_pre_update,
                 sch_on_update: Schedule::default(),
                 sch_on_last: Schedule::default(),
+                window: Some(0),
+                sch_after_draw,
                 on_start: true,
             }
         }
@@ -154,56 +291,27 @@ pub mod prelude {
     /// The root of your Bella program.
     pub struct App<'a> {
         worlds: Vec<BellaWorld>,
-
-        title: String,
-        width: u32,
-        height: u32,
-
-        new_resize: bool,
-        is_resizing: bool,
+        windows: Vec<BellaWindow<'a>>,
+        next_window_id: Arc<AtomicUsize>,
 
         context: RenderContext,
         renderers: Vec<Option<Renderer>>,
-        state: RenderState<'a>,
-        main_scene: Scene,
     }
 
     impl<'a> ApplicationHandler for App<'a> {
         fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-            let RenderState::Suspended(cached_window) = &mut self.state else {
-                return;
-            };
-
-            // Get the winit window cached in a previous Suspended event or else create a new window
-            let window = cached_window.take().unwrap_or_else(|| {
-                create_winit_window(event_loop, &self.title, self.width, self.height)
-            });
-
-            // Create a vello Surface
-            let size = window.inner_size();
-            let surface_future = self.context.create_surface(
-                window.clone(),
-                size.width,
-                size.height,
-                wgpu::PresentMode::AutoVsync,
-            );
-            let surface = pollster::block_on(surface_future).expect("Error creating surface");
-
-            // Create a vello Renderer for the surface (using its device id)
-            self.renderers
-                .resize_with(self.context.devices.len(), || None);
-            self.renderers[surface.dev_id]
-                .get_or_insert_with(|| create_vello_renderer(&self.context, &surface));
-
-            // Save the Window and Surface to a state variable
-            self.state = RenderState::Active(ActiveRenderState { window, surface });
+            for window in &mut self.windows {
+                activate_window(event_loop, &mut self.context, &mut self.renderers, window);
+            }
 
             event_loop.set_control_flow(ControlFlow::Wait);
         }
 
         fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
-            if let RenderState::Active(state) = &self.state {
-                self.state = RenderState::Suspended(Some(state.window.clone()));
+            for window in &mut self.windows {
+                if let RenderState::Active(state) = &window.state {
+                    window.state = RenderState::Suspended(Some(state.window.clone()));
+                }
             }
         }
 
@@ -213,19 +321,29 @@ pub mod prelude {
             window_id: winit::window::WindowId,
             event: WindowEvent,
         ) {
-            // Ignore the event (return from the function) if
-            //   - we have no render_state
-            //   - OR the window id of the event doesn't match the window id of our render_state
-            //
-            // Else extract a mutable reference to the render state from its containing option for use below
-            let render_state = match &mut self.state {
-                RenderState::Active(state) if state.window.id() == window_id => state,
-                _ => return,
+            // Ignore the event (return from the function) if none of our windows has a render_state
+            // with the window id of the event.
+            let Some(index) = self
+                .windows
+                .iter()
+                .position(|w| w.is_winit_window(window_id))
+            else {
+                return;
+            };
+
+            let bella_window = &mut self.windows[index];
+
+            let RenderState::Active(render_state) = &mut bella_window.state else {
+                return;
             };
 
             match event {
-                // Exit the event loop when a close is requested (e.g. window's close button is pressed)
-                WindowEvent::CloseRequested => event_loop.exit(),
+                // Exit the event loop when the close of the first window is requested (e.g. window's close button is pressed),
+                // or close the window if it's any other.
+                WindowEvent::CloseRequested => {
+                    let id = bella_window.id;
+                    self.close_window(event_loop, id);
+                }
 
                 // Resize the surface when the window is resized
                 WindowEvent::Resized(size) => {
@@ -233,15 +351,19 @@ pub mod prelude {
                         return;
                     }
 
-                    self.width = size.width;
-                    self.height = size.height;
+                    bella_window.width = size.width;
+                    bella_window.height = size.height;
 
-                    self.is_resizing = true;
-                    self.new_resize = true;
+                    bella_window.is_resizing = true;
+                    bella_window.new_resize = true;
                 }
 
                 WindowEvent::KeyboardInput { event, .. } => {
-                    for w in &self.worlds {
+                    for w in self
+                        .worlds
+                        .iter()
+                        .filter(|w| w.window == Some(bella_window.id))
+                    {
                         let input = w.main.get_resource::<Input>().unwrap();
 
                         match event.state {
@@ -256,7 +378,11 @@ pub mod prelude {
                 }
 
                 WindowEvent::CursorMoved { position, .. } => {
-                    for w in &self.worlds {
+                    for w in self
+                        .worlds
+                        .iter()
+                        .filter(|w| w.window == Some(bella_window.id))
+                    {
                         let input = w.main.get_resource::<Input>().unwrap();
 
                         input.set_mouse_pos(position.x, position.y);
@@ -264,7 +390,11 @@ pub mod prelude {
                 }
 
                 WindowEvent::MouseInput { state, button, .. } => {
-                    for w in &self.worlds {
+                    for w in self
+                        .worlds
+                        .iter()
+                        .filter(|w| w.window == Some(bella_window.id))
+                    {
                         let input = w.main.get_resource::<Input>().unwrap();
 
                         match state {
@@ -280,36 +410,43 @@ pub mod prelude {
 
                 // This is where all the rendering happens
                 WindowEvent::RedrawRequested => {
-                    if self.is_resizing {
+                    if bella_window.is_resizing {
                         render_state.window.request_redraw();
-                        self.is_resizing = false;
+                        bella_window.is_resizing = false;
                         return;
                     }
 
                     // Get the RenderSurface (surface + config)
                     let surface = &mut render_state.surface;
 
-                    let width = self.width;
-                    let height = self.height;
+                    let width = bella_window.width;
+                    let height = bella_window.height;
 
                     // This is a fix to try to smooth resizing on Windows.
-                    if self.new_resize {
+                    if bella_window.new_resize {
                         self.context.resize_surface(surface, width, height);
-                        self.new_resize = false;
+                        bella_window.new_resize = false;
                     }
 
                     let device_handle = &self.context.devices[surface.dev_id];
 
-                    self.main_scene.reset();
+                    bella_window.main_scene.reset();
 
                     let surface_texture = surface
                         .surface
                         .get_current_texture()
                         .expect("failed to get surface texture");
 
-                    let mut first_draw_call: bool = true;
+                    for w in self
+                        .worlds
+                        .iter_mut()
+                        .filter(|w| w.window == Some(bella_window.id))
+                    {
+                        w.main
+                            .get_resource_mut::<Instance>()
+                            .unwrap()
+                            .set_resolution(width, height);
 
-                    for w in &mut self.worlds {
                         if w.on_start {
                             w.sch_on_start.run(&mut w.main);
                             w.on_start = false;
@@ -318,37 +455,12 @@ pub mod prelude {
                         w.sch_on_first.run(&mut w.main);
 
                         w.sch_on_draw.run(&mut w.main);
+                        w.sch_after_draw.run(&mut w.main);
 
-                        let mut root = w.main.get_resource_mut::<Instance>().unwrap();
-
-                        root.set_resolution(self.width, self.height);
-
-                        #[allow(clippy::for_kv_map)]
-                        for (_id, scene) in &root.scenes {
-                            self.main_scene.append(scene, None);
-                        }
-
-                        self.renderers[surface.dev_id]
-                            .as_mut()
+                        w.main
+                            .get_resource::<Instance>()
                             .unwrap()
-                            .render_to_surface(
-                                &device_handle.device,
-                                &device_handle.queue,
-                                &self.main_scene,
-                                &surface_texture,
-                                &vello::RenderParams {
-                                    base_color: if first_draw_call {
-                                        first_draw_call = false;
-                                        Color::BLACK
-                                    } else {
-                                        Color::TRANSPARENT
-                                    }, // Background color
-                                    width,
-                                    height,
-                                    antialiasing_method: AaConfig::Msaa16,
-                                },
-                            )
-                            .expect("failed to render to surface");
+                            .compose(&mut bella_window.main_scene);
 
                         w.sch_on_pre_update.run(&mut w.main);
 
@@ -357,11 +469,31 @@ pub mod prelude {
                         w.sch_on_last.run(&mut w.main);
                     }
 
+                    // Every world is composed into the scene of the window, so it's rendered once, and a window without worlds is just cleared.
+                    self.renderers[surface.dev_id]
+                        .as_mut()
+                        .unwrap()
+                        .render_to_surface(
+                            &device_handle.device,
+                            &device_handle.queue,
+                            &bella_window.main_scene,
+                            &surface_texture,
+                            &vello::RenderParams {
+                                base_color: Color::BLACK, // Background color
+                                width,
+                                height,
+                                antialiasing_method: AaConfig::Msaa16,
+                            },
+                        )
+                        .expect("failed to render to surface");
+
                     surface_texture.present();
 
                     device_handle.device.poll(wgpu::Maintain::Poll);
 
                     render_state.window.request_redraw();
+
+                    self.apply_window_commands(event_loop);
                 }
                 _ => {}
             }
@@ -372,26 +504,37 @@ pub mod prelude {
         /// Creates a new [`App`] with a window ready to go.
         /// `title` sets the title of the window, `width` and `height` set the resolution.
         pub fn new(title: &str, width: u32, height: u32) -> Self {
-            Self {
+            let mut app = Self {
                 worlds: vec![],
-
-                title: title.to_string(),
-                width,
-                height,
-
-                is_resizing: false,
-                new_resize: false,
+                windows: vec![],
+                next_window_id: Arc::new(AtomicUsize::new(0)),
 
                 context: RenderContext::new(),
                 renderers: vec![],
-                state: RenderState::Suspended(None),
-                main_scene: Scene::new(),
-            }
+            };
+
+            app.new_window(title, width, height);
+            app
+        }
+
+        /// Creates a new window. Every world created after it will be drawn into this window.
+        pub fn new_window(&mut self, title: &str, width: u32, height: u32) -> &mut Self {
+            let id = self.next_window_id.fetch_add(1, Ordering::Relaxed);
+            self.windows
+                .push(BellaWindow::new(id, title, width, height));
+            self
         }
 
-        /// Creates a new world.
+        /// Creates a new world, drawn into the last window created.
         pub fn new_world(&mut self) -> &mut Self {
-            self.worlds.push(BellaWorld::new());
+            let mut world = BellaWorld::new();
+
+            world.window = Some(self.windows.last().map(|w| w.id).unwrap_or_default());
+            world
+                .main
+                .insert_resource(Windows::new(world.window, self.next_window_id.clone()));
+
+            self.worlds.push(world);
             self
         }
 
@@ -407,12 +550,15 @@ pub mod prelude {
 
         /// Adds a system that'll be executed in the render loop.
         /// This is used for rendering the Vello Shapes, for example.
+        ///
+        /// These systems run after [`draw_shapes`], so whatever they draw directly into a scene goes over its shapes.
+        /// Fragments of a [`SceneWriter`] are appended after all of them.
         pub fn on_draw<M>(&mut self, systems: impl IntoSystemConfigs<M>) -> &mut Self {
             self.worlds
                 .last_mut()
                 .unwrap()
                 .sch_on_draw
-                .add_systems(systems);
+                .add_systems(systems.after(draw_shapes));
             self
         }
 
@@ -427,6 +573,84 @@ pub mod prelude {
             self
         }
 
+        /// Applies all of the requests sent through the [`Windows`] resource of every world.
+        fn apply_window_commands(&mut self, event_loop: &ActiveEventLoop) {
+            let mut commands = vec![];
+
+            for w in &self.worlds {
+                let windows = w.main.get_resource::<Windows>().unwrap();
+
+                while let Some(command) = windows.pop_command() {
+                    commands.push(command);
+                }
+            }
+
+            for command in commands {
+                match command {
+                    WindowCommand::Open {
+                        id,
+                        title,
+                        width,
+                        height,
+                        worlds,
+                    } => {
+                        let mut window = BellaWindow::new(id, &title, width, height);
+                        activate_window(
+                            event_loop,
+                            &mut self.context,
+                            &mut self.renderers,
+                            &mut window,
+                        );
+                        self.windows.push(window);
+
+                        for world in worlds {
+                            self.assign_world(world, id);
+                        }
+                    }
+                    WindowCommand::Close(id) => {
+                        self.close_window(event_loop, id);
+                    }
+                    WindowCommand::Assign { world, window } => {
+                        self.assign_world(world, window);
+                    }
+                }
+            }
+        }
+
+        /// Closes a window and unassigns its worlds, or exits if it's the first window.
+        fn close_window(&mut self, event_loop: &ActiveEventLoop, id: usize) {
+            if self.windows.first().is_some_and(|w| w.id == id) {
+                event_loop.exit();
+                return;
+            }
+
+            self.windows.retain(|w| w.id != id);
+
+            for world in 0..self.worlds.len() {
+                if self.worlds[world].window == Some(id) {
+                    self.set_world_window(world, None);
+                }
+            }
+        }
+
+        fn assign_world(&mut self, world: usize, window: usize) {
+            if self.windows.iter().any(|w| w.id == window) {
+                self.set_world_window(world, Some(window));
+            }
+        }
+
+        fn set_world_window(&mut self, world: usize, window: Option<usize>) {
+            let Some(w) = self.worlds.get_mut(world) else {
+                return;
+            };
+
+            w.window = window;
+            w.main
+                .get_resource_mut::<Windows>()
+                .unwrap()
+                .set_current(window);
+        }
+
         /// Runs your [`App`].
         pub fn run(&mut self) {
             let event_loop = EventLoop::new().unwrap();
@@ -0,0 +1,419 @@
+//! Texts made of spans with their own fonts, sizes, colors and decorations, and a small markup to write them.
+
+use crate::assets::AssetServer;
+use std::fmt::{self, Display};
+use vello::peniko::{Color, Font};
+
+/// How a span of a [`RichText`] looks. What isn't set comes from the layout it's drawn with.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextStyle {
+    /// Tried before the fonts of the layout, which are its fallbacks.
+    pub font: Option<Font>,
+    pub size: Option<f64>,
+    /// Replaces the brush the text is drawn with.
+    pub color: Option<Color>,
+    /// From 100 (thin) to 900 (black), 400 being regular. Only fonts with a weight axis can change it: the others get thicker glyphs from 600 on, a bold font looking better.
+    pub weight: Option<f32>,
+    /// In percent of the normal width, like 75 for condensed. Only fonts with a width axis can change it.
+    pub width: Option<f32>,
+    /// In degrees, negative values leaning to the right. Only fonts with a slant axis can change it.
+    pub slant: Option<f32>,
+    /// Other axes of variable fonts, by their tag, like `("GRAD", 50.0)`. See [`FontInfo::axes`](crate::assets::FontInfo::axes) for what a font has.
+    pub variations: Vec<(String, f32)>,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+impl TextStyle {
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_slant(mut self, slant: f32) -> Self {
+        self.slant = Some(slant);
+        self
+    }
+
+    /// Sets an axis of variable fonts, replacing its previous value.
+    pub fn with_variation(mut self, tag: &str, value: f32) -> Self {
+        self.variations.retain(|(t, _)| t != tag);
+        self.variations.push((tag.to_string(), value));
+        self
+    }
+
+    /// The settings of every axis this style changes, to give to the font.
+    pub fn axis_settings(&self) -> Vec<(&str, f32)> {
+        let named = [
+            ("wght", self.weight),
+            ("wdth", self.width),
+            ("slnt", self.slant),
+        ];
+
+        named
+            .into_iter()
+            .filter_map(|(tag, value)| Some((tag, value?)))
+            .chain(
+                self.variations
+                    .iter()
+                    .map(|(tag, value)| (tag.as_str(), *value)),
+            )
+            .collect()
+    }
+
+    pub fn with_underline(mut self, underline: bool) -> Self {
+        self.underline = underline;
+        self
+    }
+
+    pub fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = strikethrough;
+        self
+    }
+}
+
+/// A part of a [`RichText`] with a single style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+/// A text made of spans that each have their own style, to be laid out with `TextLayout::rich` or drawn with `fill_rich_text`.
+///
+/// It can be built span by span, or parsed from a markup where tags style the text between them:
+///
+/// - `<b>bold</b>`, `<u>underlined</u>` and `<s>struck through</s>`.
+/// - `<color=#ff8000>`, with an optional alpha like `#ff800080`.
+/// - `<size=32>`, `<weight=600>`, `<width=75>` and `<slant=-12>`.
+/// - `<axis=GRAD:50>`, for the other axes of variable fonts.
+/// - `<font=name>`, with the name of a font in the [`AssetServer`], only with [`RichText::parse_with_assets`].
+///
+/// Tags can be nested, and `\` writes the next character as it is, like `\<`. Sizes and widths must be positive, weights are clamped between 1 and 1000, and slants between -90 and 90.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichText {
+    pub spans: Vec<TextSpan>,
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_span(mut self, text: impl Into<String>, style: TextStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    pub fn push(&mut self, text: impl Into<String>, style: TextStyle) {
+        self.spans.push(TextSpan {
+            text: text.into(),
+            style,
+        });
+    }
+
+    pub fn parse(markup: &str) -> Result<Self, MarkupError> {
+        parse_markup(markup, None)
+    }
+
+    /// Parses a markup whose `<font=name>` tags use the fonts of `assets`, by the name or path they were loaded with.
+    pub fn parse_with_assets(markup: &str, assets: &AssetServer) -> Result<Self, MarkupError> {
+        parse_markup(markup, Some(assets))
+    }
+}
+
+/// The text of every span, without their styles.
+impl Display for RichText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for span in &self.spans {
+            f.write_str(&span.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        Self::new().with_span(text, TextStyle::default())
+    }
+}
+
+/// Why a markup couldn't be parsed by [`RichText::parse`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupError {
+    UnknownTag(String),
+    /// A tag that was opened but never closed, or a `<` without its `>`.
+    UnclosedTag(String),
+    /// A closing tag that doesn't match the last opened one.
+    UnexpectedClosingTag(String),
+    InvalidValue {
+        tag: String,
+        value: String,
+    },
+    UnknownFont(String),
+}
+
+impl Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTag(tag) => write!(f, "unknown tag <{tag}>"),
+            Self::UnclosedTag(tag) => write!(f, "tag <{tag}> is never closed"),
+            Self::UnexpectedClosingTag(tag) => write!(f, "unexpected closing tag </{tag}>"),
+            Self::InvalidValue { tag, value } => write!(f, "invalid value {value:?} for <{tag}>"),
+            Self::UnknownFont(name) => write!(f, "unknown font {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+fn parse_markup(markup: &str, assets: Option<&AssetServer>) -> Result<RichText, MarkupError> {
+    let mut text = RichText::new();
+    let mut stack: Vec<(String, TextStyle)> = vec![];
+    let mut current = String::new();
+    let mut chars = markup.chars();
+
+    let style =
+        |stack: &[(String, TextStyle)]| stack.last().map(|s| s.1.clone()).unwrap_or_default();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => current.extend(chars.next()),
+            '<' => {
+                let mut tag = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('>') => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(MarkupError::UnclosedTag(tag)),
+                    }
+                }
+
+                if !current.is_empty() {
+                    text.push(std::mem::take(&mut current), style(&stack));
+                }
+
+                if let Some(name) = tag.strip_prefix('/') {
+                    let name = name.trim();
+
+                    match stack.pop() {
+                        Some((open, _)) if open == name => {}
+                        _ => return Err(MarkupError::UnexpectedClosingTag(name.to_string())),
+                    }
+                } else {
+                    let (name, value) = match tag.split_once('=') {
+                        Some((name, value)) => (name.trim(), Some(value.trim())),
+                        None => (tag.trim(), None),
+                    };
+
+                    let style = apply_tag(style(&stack), name, value, assets)?;
+                    stack.push((name.to_string(), style));
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if let Some((name, _)) = stack.pop() {
+        return Err(MarkupError::UnclosedTag(name));
+    }
+
+    if !current.is_empty() {
+        text.push(current, TextStyle::default());
+    }
+
+    Ok(text)
+}
+
+fn apply_tag(
+    style: TextStyle,
+    name: &str,
+    value: Option<&str>,
+    assets: Option<&AssetServer>,
+) -> Result<TextStyle, MarkupError> {
+    let invalid = || MarkupError::InvalidValue {
+        tag: name.to_string(),
+        value: value.unwrap_or_default().to_string(),
+    };
+
+    match name {
+        "b" => Ok(style.with_weight(700.0)),
+        "u" => Ok(style.with_underline(true)),
+        "s" => Ok(style.with_strikethrough(true)),
+        "color" => {
+            let color = value.and_then(parse_color).ok_or_else(invalid)?;
+            Ok(style.with_color(color))
+        }
+        "size" => {
+            let size = value
+                .and_then(parse_number)
+                .filter(|s| *s > 0.0)
+                .ok_or_else(invalid)?;
+            Ok(style.with_size(size))
+        }
+        "weight" => {
+            let weight = value.and_then(parse_number).ok_or_else(invalid)?;
+            Ok(style.with_weight(weight.clamp(1.0, 1000.0) as f32))
+        }
+        "width" => {
+            let width = value
+                .and_then(parse_number)
+                .filter(|w| *w > 0.0)
+                .ok_or_else(invalid)?;
+            Ok(style.with_width(width as f32))
+        }
+        "slant" => {
+            let slant = value.and_then(parse_number).ok_or_else(invalid)?;
+            Ok(style.with_slant(slant.clamp(-90.0, 90.0) as f32))
+        }
+        "axis" => {
+            let (tag, axis) = value
+                .and_then(|v| v.split_once(':'))
+                .and_then(|(tag, v)| Some((tag.trim(), parse_number(v.trim())?)))
+                .filter(|(tag, _)| tag.len() == 4 && tag.is_ascii())
+                .ok_or_else(invalid)?;
+            Ok(style.with_variation(tag, axis as f32))
+        }
+        "font" => {
+            let name = value.ok_or_else(invalid)?;
+            let font = assets
+                .and_then(|a| a.get::<Font>(name))
+                .ok_or_else(|| MarkupError::UnknownFont(name.to_string()))?;
+            Ok(style.with_font(font.clone()))
+        }
+        _ => Err(MarkupError::UnknownTag(name.to_string())),
+    }
+}
+
+/// Parses a number, without the infinities and `NaN`.
+fn parse_number(value: &str) -> Option<f64> {
+    value.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Parses `#rrggbb` or `#rrggbbaa`.
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+
+    Some(Color::rgba8(channel(0)?, channel(2)?, channel(4)?, alpha))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(tag: &str, value: &str) -> MarkupError {
+        MarkupError::InvalidValue {
+            tag: tag.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn nested_tags() {
+        let text = RichText::parse("a <b>b <u>c</u></b> d").unwrap();
+        let spans: Vec<(&str, &TextStyle)> = text
+            .spans
+            .iter()
+            .map(|s| (s.text.as_str(), &s.style))
+            .collect();
+
+        let bold = TextStyle::default().with_weight(700.0);
+        assert_eq!(
+            spans,
+            [
+                ("a ", &TextStyle::default()),
+                ("b ", &bold),
+                ("c", &bold.clone().with_underline(true)),
+                (" d", &TextStyle::default()),
+            ]
+        );
+        assert_eq!(text.to_string(), "a b c d");
+    }
+
+    #[test]
+    fn values() {
+        let text = RichText::parse(
+            "<color=#ff800080><size=32><weight=2000><axis=GRAD:50>x</axis></weight></size></color>",
+        )
+        .unwrap();
+        let style = &text.spans[0].style;
+
+        assert_eq!(style.color, Some(Color::rgba8(255, 128, 0, 128)));
+        assert_eq!(style.size, Some(32.0));
+        assert_eq!(style.weight, Some(1000.0));
+        assert_eq!(style.variations, [("GRAD".to_string(), 50.0)]);
+        assert_eq!(
+            RichText::parse("<color=#00ff00>x</color>").unwrap().spans[0]
+                .style
+                .color,
+            Some(Color::rgba8(0, 255, 0, 255))
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        let text = RichText::parse(r"\<b>\\ <s>\</s></s>").unwrap();
+
+        assert_eq!(text.spans[0].text, r"<b>\ ");
+        assert_eq!(text.spans[1].text, "</s>");
+        assert!(text.spans[1].style.strikethrough);
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |markup| RichText::parse(markup).unwrap_err();
+
+        assert_eq!(parse("<b>x"), MarkupError::UnclosedTag("b".to_string()));
+        assert_eq!(parse("<b x"), MarkupError::UnclosedTag("b x".to_string()));
+        assert_eq!(
+            parse("<b><u>x</b></u>"),
+            MarkupError::UnexpectedClosingTag("b".to_string())
+        );
+        assert_eq!(
+            parse("x</b>"),
+            MarkupError::UnexpectedClosingTag("b".to_string())
+        );
+        assert_eq!(parse("<i>x</i>"), MarkupError::UnknownTag("i".to_string()));
+        assert_eq!(parse("<color=red>x</color>"), invalid("color", "red"));
+        assert_eq!(parse("<color=#ff80>x</color>"), invalid("color", "#ff80"));
+        assert_eq!(parse("<size=NaN>x</size>"), invalid("size", "NaN"));
+        assert_eq!(parse("<size=inf>x</size>"), invalid("size", "inf"));
+        assert_eq!(parse("<size=0>x</size>"), invalid("size", "0"));
+        assert_eq!(parse("<size=-4>x</size>"), invalid("size", "-4"));
+        assert_eq!(parse("<weight>x</weight>"), invalid("weight", ""));
+        assert_eq!(parse("<axis=GRADE:1>x</axis>"), invalid("axis", "GRADE:1"));
+        assert_eq!(
+            parse("<font=a>x</font>"),
+            MarkupError::UnknownFont("a".to_string())
+        );
+    }
+}
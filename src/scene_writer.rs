@@ -0,0 +1,113 @@
+//! Drawing into the scenes of the [`Instance`] from many systems at the same time.
+//!
+//! Systems that take `ResMut<Instance>` can't run in parallel. A [`SceneWriter`] only needs to read the [`Instance`], and draws into scenes of its own that are merged afterwards.
+
+use crate::prelude::*;
+use bevy_ecs::system::SystemParam;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use vello::Scene;
+
+/// The fragments drawn by every [`SceneWriter`] on this frame, waiting to be merged into the scenes of the [`Instance`].
+#[derive(Resource, Default)]
+pub struct SceneFragments {
+    next_writer: AtomicUsize,
+    fragments: Mutex<BTreeMap<(SceneId, i32, usize), Scene>>,
+}
+
+/// The state each system keeps for its [`SceneWriter`].
+///
+/// The writers are numbered when their systems are initialized, which bevy doesn't guarantee to follow the order the systems were added in. Use [`SceneWriter::set_order`] when the order matters.
+pub struct SceneWriterState {
+    writer: usize,
+    order: i32,
+    scenes: HashMap<SceneId, Scene>,
+}
+
+impl FromWorld for SceneWriterState {
+    fn from_world(world: &mut World) -> Self {
+        let writer = world
+            .get_resource_or_insert_with(SceneFragments::default)
+            .next_writer
+            .fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            writer,
+            order: 0,
+            scenes: HashMap::new(),
+        }
+    }
+}
+
+/// Draws into the scenes of the [`Instance`] without locking it, so draw systems using it can run in parallel.
+///
+/// What's drawn is appended to the scenes once the `on_draw` systems are done, on top of what was drawn directly into them. Between writers, the ones with a lower [`SceneWriter::set_order`] are drawn first, and writers with the same order are drawn in an unspecified (but stable) order.
+///
+/// ```ignore
+/// fn draw(mut writer: SceneWriter) {
+///     if let Some(scene) = writer.scene("scene") {
+///         scene.fill_circle(Fill::NonZero, Affine::IDENTITY, Color::RED, 50.0);
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct SceneWriter<'w, 's> {
+    instance: Res<'w, Instance>,
+    fragments: Res<'w, SceneFragments>,
+    state: Local<'s, SceneWriterState>,
+}
+
+impl SceneWriter<'_, '_> {
+    /// Gets the fragment of a scene for this system.
+    ///
+    /// Returns `None` if the scene doesn't exist, or if it's persistent and isn't being redrawn on this frame.
+    pub fn scene(&mut self, key: impl SceneKey) -> Option<&mut Scene> {
+        let id = key.scene_id(&self.instance)?;
+
+        if !self.instance.needs_redraw(id) {
+            return None;
+        }
+
+        Some(self.state.scenes.entry(id).or_default())
+    }
+
+    /// Sets where the fragments of this system go among the other writers, lower orders being drawn first. It's `0` by default, and it's kept between frames.
+    pub fn set_order(&mut self, order: i32) {
+        self.state.order = order;
+    }
+
+    pub fn instance(&self) -> &Instance {
+        &self.instance
+    }
+}
+
+impl Drop for SceneWriter<'_, '_> {
+    fn drop(&mut self) {
+        if self.state.scenes.is_empty() {
+            return;
+        }
+
+        let writer = self.state.writer;
+        let order = self.state.order;
+        let mut fragments = self.fragments.fragments.lock().unwrap();
+
+        for (id, scene) in self.state.scenes.drain() {
+            fragments.insert((id, order, writer), scene);
+        }
+    }
+}
+
+/// Appends the fragments of every [`SceneWriter`] to their scenes, ordered by scene, then by order, and then by writer.
+pub fn merge_scene_fragments(
+    mut fragments: ResMut<SceneFragments>,
+    mut instance: ResMut<Instance>,
+) {
+    let fragments = std::mem::take(fragments.fragments.get_mut().unwrap());
+
+    for ((id, _, _), fragment) in fragments {
+        if let Some(scene) = instance.get_scene(id) {
+            scene.append(&fragment, None);
+        }
+    }
+}
@@ -0,0 +1,392 @@
+//! Shapes and texts that are drawn automatically, without writing an `on_draw` system.
+//!
+//! Spawn an entity with a [`Transform`], a [`SceneTarget`], one of the shape components and a [`FillStyle`] and/or a [`StrokeStyle`], and [`draw_shapes`] will take care of the rest.
+
+use crate::layers::push_entity_layer;
+use crate::prelude::*;
+use bevy_ecs::query::QueryData;
+use kurbo::{BezPath, Circle, Point, Rect, RoundedRect, Stroke, Vec2};
+use peniko::{Color, Fill, Font};
+use std::collections::HashMap;
+
+/// The scene of the [`Instance`] an entity is drawn into.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SceneTarget(pub SceneId);
+
+/// A circle centered on the entity.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShapeCircle {
+    pub radius: f64,
+}
+
+impl ShapeCircle {
+    pub fn to_shape(&self) -> Circle {
+        Circle::new(Point::ZERO, self.radius)
+    }
+}
+
+/// A rounded rectangle centered on the entity.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShapeRoundedRect {
+    pub size: Vec2,
+    pub corner: f64,
+}
+
+impl ShapeRoundedRect {
+    pub fn to_shape(&self) -> RoundedRect {
+        RoundedRect::from_rect(
+            Rect::from_center_size(Point::ZERO, self.size.to_size()),
+            self.corner,
+        )
+    }
+}
+
+/// Any kind of path, relative to the entity.
+#[derive(Component, Debug, Clone)]
+pub struct ShapePath(pub BezPath);
+
+/// A text, drawn with the brush of the entity's [`FillStyle`], and outlined under it by its [`TextOutline`].
+#[derive(Component, Debug, Clone)]
+pub struct TextLabel {
+    pub text: String,
+    pub font: Font,
+    pub size: f64,
+}
+
+impl TextLabel {
+    /// Shapes the text. This is slow, so [`compute_bounds`] only does it when the label changes, and keeps the result in a [`TextLabelLayout`].
+    pub fn layout(&self) -> TextLayout {
+        TextLayout::new(&self.text, std::slice::from_ref(&self.font), self.size)
+    }
+}
+
+/// The shaped [`TextLabel`] of an entity, drawn by [`draw_shapes`]. Kept up to date by [`compute_bounds`].
+#[derive(Component, Debug, Clone)]
+pub struct TextLabelLayout(pub TextLayout);
+
+/// Fills the shape or text of the entity.
+#[derive(Component, Debug, Clone)]
+pub struct FillStyle {
+    pub fill: Fill,
+    pub brush: Brush,
+}
+
+impl FillStyle {
+    pub fn new(brush: impl Into<Brush>) -> Self {
+        Self {
+            fill: Fill::NonZero,
+            brush: brush.into(),
+        }
+    }
+}
+
+impl Default for FillStyle {
+    fn default() -> Self {
+        Self::new(Color::WHITE)
+    }
+}
+
+/// Outlines the shape of the entity.
+#[derive(Component, Debug, Clone)]
+pub struct StrokeStyle {
+    pub stroke: Stroke,
+    pub brush: Brush,
+}
+
+impl StrokeStyle {
+    pub fn new(width: f64, brush: impl Into<Brush>) -> Self {
+        Self {
+            stroke: Stroke::new(width),
+            brush: brush.into(),
+        }
+    }
+
+    /// Makes the stroke dashed. `pattern` alternates between the lengths of the dashes and of the gaps, and `offset` moves them along the path.
+    pub fn with_dashes(mut self, offset: f64, pattern: impl IntoIterator<Item = f64>) -> Self {
+        self.stroke = self.stroke.with_dashes(offset, pattern);
+        self
+    }
+}
+
+/// Outlines the [`TextLabel`] of the entity under its fill, which keeps it readable on busy backgrounds. Only the outer half of the stroke shows.
+///
+/// It's separate from [`StrokeStyle`], which only outlines shapes, so that a button can have a border and a text without an outline.
+#[derive(Component, Debug, Clone)]
+pub struct TextOutline {
+    pub stroke: Stroke,
+    pub brush: Brush,
+}
+
+impl TextOutline {
+    pub fn new(width: f64, brush: impl Into<Brush>) -> Self {
+        Self {
+            stroke: Stroke::new(width),
+            brush: brush.into(),
+        }
+    }
+}
+
+/// A blurred shadow under a [`ShapeRoundedRect`] or a [`ShapeCircle`]. With no offset, it works as a glow.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Shadow {
+    pub offset: Vec2,
+    /// The standard deviation of the blur.
+    pub blur: f64,
+    pub color: Color,
+    /// How much bigger than the shape the shadow is, on each side.
+    pub spread: f64,
+}
+
+impl Shadow {
+    pub fn new(offset: Vec2, blur: f64, color: Color) -> Self {
+        Self {
+            offset,
+            blur,
+            color,
+            spread: 0.0,
+        }
+    }
+
+    /// A shadow right under the shape, spreading out of it.
+    pub fn glow(blur: f64, spread: f64, color: Color) -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            blur,
+            color,
+            spread,
+        }
+    }
+
+    pub fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// How far the shadow can reach out of the shape, on each side. The blur fades out after about three times its standard deviation.
+    pub fn extent(&self) -> f64 {
+        self.spread + self.blur * 3.0 + self.offset.x.abs().max(self.offset.y.abs())
+    }
+}
+
+#[derive(QueryData)]
+pub struct ShapeQuery {
+    entity: Entity,
+    transform: &'static GlobalTransform,
+    target: &'static SceneTarget,
+    circle: Option<&'static ShapeCircle>,
+    rounded_rect: Option<&'static ShapeRoundedRect>,
+    path: Option<&'static ShapePath>,
+    text: Option<&'static TextLabelLayout>,
+    text_outline: Option<&'static TextOutline>,
+    sprite: Option<&'static VectorSprite>,
+    fill: Option<&'static FillStyle>,
+    stroke: Option<&'static StrokeStyle>,
+    shadow: Option<&'static Shadow>,
+    visibility: Option<&'static InheritedVisibility>,
+    aabb: Option<&'static Aabb>,
+    clip: Option<&'static ClipShape>,
+    opacity: Option<&'static Opacity>,
+    blend: Option<&'static Blend>,
+    anchor: Option<&'static Anchor>,
+    trim: Option<&'static Trim>,
+}
+
+impl ShapeQueryItem<'_> {
+    fn has_layer(&self) -> bool {
+        self.clip.is_some() || self.opacity.is_some() || self.blend.is_some()
+    }
+}
+
+/// Draws every entity with a shape, a text or a [`VectorSprite`] into its [`SceneTarget`], back-to-front by their [`ZIndex`].
+///
+/// Shapes and texts are placed from the entity's [`Anchor`], and strokes are cut by its [`Trim`], if it has them.
+///
+/// Entities with a [`ClipShape`], an [`Opacity`] or a [`Blend`] are drawn in a layer along with their children, which are sorted among themselves.
+///
+/// Entities that are hidden, or whose [`Aabb`] is outside of every view, are skipped and counted in [`CullingStats`]. Entities in persistent scenes are never culled.
+pub fn draw_shapes(
+    query: Query<ShapeQuery>,
+    parents: Query<&Parent>,
+    z_indices: Query<&ZIndex>,
+    mut instance: ResMut<Instance>,
+    mut stats: ResMut<CullingStats>,
+) {
+    stats.reset();
+
+    let shapes: Vec<_> = query.iter_z_sorted(&z_indices).collect();
+    let index: HashMap<Entity, usize> = shapes
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.entity, i))
+        .collect();
+
+    // The entities drawn in the layer of one of their parents, grouped by that parent.
+    let mut members: HashMap<Entity, Vec<usize>> = HashMap::new();
+    let mut roots = vec![];
+
+    for (i, shape) in shapes.iter().enumerate() {
+        match layer_owner(shape, &shapes, &index, &parents) {
+            Some(owner) => members.entry(owner).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    for i in roots {
+        draw_entry(i, &shapes, &members, &mut instance, &mut stats);
+    }
+}
+
+/// Finds the closest parent of an entity that has a layer and is drawn into the same scene.
+fn layer_owner(
+    shape: &ShapeQueryItem,
+    shapes: &[ShapeQueryItem],
+    index: &HashMap<Entity, usize>,
+    parents: &Query<&Parent>,
+) -> Option<Entity> {
+    let mut current = parents.get(shape.entity).ok()?.0;
+
+    loop {
+        if let Some(parent) = index.get(&current).map(|i| &shapes[*i]) {
+            if parent.has_layer() && parent.target.0 == shape.target.0 {
+                return Some(current);
+            }
+        }
+
+        current = parents.get(current).ok()?.0;
+    }
+}
+
+/// Draws an entity, and the members of its layer if it has one.
+fn draw_entry(
+    i: usize,
+    shapes: &[ShapeQueryItem],
+    members: &HashMap<Entity, Vec<usize>>,
+    instance: &mut Instance,
+    stats: &mut CullingStats,
+) {
+    let shape = &shapes[i];
+
+    if !instance.needs_redraw(shape.target.0) {
+        return;
+    }
+
+    let layer = match instance.get_scene(shape.target.0) {
+        Some(scene) => push_entity_layer(
+            scene,
+            shape.transform.affine,
+            shape.clip,
+            shape.opacity,
+            shape.blend,
+        ),
+        None => return,
+    };
+
+    draw_shape(shape, instance, stats);
+
+    for j in members.get(&shape.entity).into_iter().flatten() {
+        draw_entry(*j, shapes, members, instance, stats);
+    }
+
+    if layer {
+        if let Some(scene) = instance.get_scene(shape.target.0) {
+            scene.pop_layer();
+        }
+    }
+}
+
+fn draw_shape(shape: &ShapeQueryItem, instance: &mut Instance, stats: &mut CullingStats) {
+    if shape.visibility.is_some_and(|v| !v.0) {
+        stats.hidden += 1;
+        return;
+    }
+
+    // Persistent scenes aren't encoded again when the views move, so nothing in them can be culled.
+    let persistent = instance
+        .scene_properties(shape.target.0)
+        .is_some_and(|p| p.persistent);
+
+    if let Some(aabb) = shape.aabb.filter(|_| !persistent) {
+        let bounds = shape.transform.affine.transform_rect_bbox(aabb.0);
+
+        if !instance.is_rect_visible(shape.target.0, bounds) {
+            stats.culled += 1;
+            return;
+        }
+    }
+
+    stats.drawn += 1;
+
+    let Some(scene) = instance.get_scene(shape.target.0) else {
+        return;
+    };
+
+    match (shape.anchor, shape.trim) {
+        (Some(anchor), Some(trim)) => {
+            draw_into(&mut scene.anchored(*anchor).trimmed(*trim), shape);
+        }
+        (Some(anchor), None) => draw_into(&mut scene.anchored(*anchor), shape),
+        (None, Some(trim)) => draw_into(&mut scene.trimmed(*trim), shape),
+        (None, None) => draw_into(scene, shape),
+    }
+}
+
+fn draw_into(target: &mut impl DrawTarget, shape: &ShapeQueryItem) {
+    let t = shape.transform.affine;
+
+    if let Some(s) = shape.shadow {
+        if let Some(c) = shape.circle {
+            let size = Vec2::new(c.radius * 2.0, c.radius * 2.0);
+            target.fill_shadow(t, size, c.radius, s);
+        }
+
+        if let Some(r) = shape.rounded_rect {
+            target.fill_shadow(t, r.size, r.corner, s);
+        }
+    }
+
+    if let Some(s) = shape.sprite {
+        let t = s.bounds().map_or(t, |b| target.anchored_transform(t, b));
+        s.draw(target.scene(), t);
+    }
+
+    if let Some(fill) = shape.fill {
+        if let Some(c) = shape.circle {
+            target.fill_brush(fill.fill, t, &fill.brush, &c.to_shape());
+        }
+
+        if let Some(r) = shape.rounded_rect {
+            target.fill_brush(fill.fill, t, &fill.brush, &r.to_shape());
+        }
+
+        if let Some(p) = shape.path {
+            target.fill_brush(fill.fill, t, &fill.brush, &p.0);
+        }
+    }
+
+    if let Some(TextLabelLayout(layout)) = shape.text {
+        // The outline of a text goes over the shapes but under its fill, so that it doesn't cover the thin parts of the glyphs.
+        if let Some(outline) = shape.text_outline {
+            let brush = outline.brush.resolve(layout.bounds);
+            target.stroke_layout(outline.stroke.clone(), layout, t, &brush);
+        }
+
+        if let Some(fill) = shape.fill {
+            let brush = fill.brush.resolve(layout.bounds);
+            target.fill_layout(fill.fill, layout, t, &brush);
+        }
+    }
+
+    if let Some(stroke) = shape.stroke {
+        if let Some(c) = shape.circle {
+            target.stroke_brush(&stroke.stroke, t, &stroke.brush, &c.to_shape());
+        }
+
+        if let Some(r) = shape.rounded_rect {
+            target.stroke_brush(&stroke.stroke, t, &stroke.brush, &r.to_shape());
+        }
+
+        if let Some(p) = shape.path {
+            target.stroke_brush(&stroke.stroke, t, &stroke.brush, &p.0);
+        }
+    }
+}
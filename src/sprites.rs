@@ -0,0 +1,59 @@
+//! Vector "sprites": shapes encoded once and drawn as many times as needed.
+
+use crate::prelude::*;
+use core::fmt::Debug;
+use kurbo::{Affine, Rect};
+use std::sync::Arc;
+use vello::Scene;
+
+/// A pre-encoded piece of a scene that can be drawn many times, each with its own transformation, without encoding its shapes again.
+///
+/// Cloning a [`VectorSprite`] is cheap, as the encoding is shared. It can be stored in the [`AssetServer`] with [`AssetServer::insert`], and spawned as a component along with a [`Transform`] and a [`SceneTarget`] to be drawn automatically.
+#[derive(Component, Clone)]
+pub struct VectorSprite {
+    fragment: Arc<Scene>,
+    bounds: Option<Rect>,
+}
+
+impl Debug for VectorSprite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VectorSprite")
+            .field("bounds", &self.bounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl VectorSprite {
+    /// Creates a sprite by drawing into an empty scene.
+    pub fn new(draw: impl FnOnce(&mut Scene)) -> Self {
+        let mut fragment = Scene::new();
+        draw(&mut fragment);
+        Self::from_scene(fragment)
+    }
+
+    pub fn from_scene(fragment: Scene) -> Self {
+        Self {
+            fragment: Arc::new(fragment),
+            bounds: None,
+        }
+    }
+
+    /// Sets the area the sprite covers, relative to its origin. Sprites with bounds can be culled when they're outside of the view.
+    pub fn with_bounds(mut self, bounds: Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    pub fn fragment(&self) -> &Scene {
+        &self.fragment
+    }
+
+    /// Draws the sprite into a scene.
+    pub fn draw(&self, scene: &mut Scene, t: Affine) {
+        scene.append(&self.fragment, Some(t));
+    }
+}
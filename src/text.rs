@@ -0,0 +1,1166 @@
+//! Shaping and laying out texts, with kerning, ligatures, combining marks, right-to-left scripts and fallback fonts.
+
+use crate::rich_text::{RichText, TextStyle};
+use std::cell::RefCell;
+use std::ops::Range;
+use swash::shape::cluster::Glyph as ShapedGlyph;
+use swash::shape::{Direction, ShapeContext};
+use swash::text::cluster::{Boundary, CharCluster, CharInfo, Parser, Status, Token};
+use swash::text::{analyze, Script};
+use swash::{tag_from_bytes, FontRef};
+use unicode_bidi::ParagraphBidiInfo;
+use vello::kurbo::{Rect, Vec2};
+use vello::peniko::{Color, Font};
+use vello::skrifa::instance::NormalizedCoord;
+use vello::Glyph;
+
+thread_local! {
+    // Shaping keeps caches of the fonts it has seen, so each thread reuses its own.
+    static SHAPE_CONTEXT: RefCell<ShapeContext> = RefCell::new(ShapeContext::new());
+}
+
+/// Glyphs of a single font and style, placed relative to the first baseline of the text.
+#[derive(Debug, Clone)]
+pub struct GlyphRun {
+    pub font: Font,
+    pub font_size: f32,
+    /// The variations of the font, like its weight.
+    pub normalized_coords: Vec<NormalizedCoord>,
+    pub glyphs: Vec<Glyph>,
+    pub rtl: bool,
+    /// Replaces the brush of the layout, for the spans of a [`RichText`] that have a color.
+    pub color: Option<Color>,
+    /// Set when the text is bold but the font has no weight axis, so its glyphs are thickened by stroking them too.
+    pub synthetic_bold: bool,
+}
+
+/// An underline or a strikethrough.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextDecoration {
+    pub rect: Rect,
+    pub color: Option<Color>,
+}
+
+/// A line of a [`TextLayout`], with its glyphs in the order they're drawn, from left to right.
+#[derive(Debug, Clone)]
+pub struct TextLine {
+    /// Where the line is in the text, in bytes.
+    pub range: Range<usize>,
+    pub runs: Vec<GlyphRun>,
+    pub decorations: Vec<TextDecoration>,
+    /// Where the line starts, once aligned.
+    pub x: f64,
+    pub width: f64,
+    pub baseline: f64,
+    /// How far the biggest text of the line goes above the baseline.
+    pub ascent: f64,
+    /// How far the biggest text of the line goes below the baseline.
+    pub descent: f64,
+    /// Whether the paragraph of the line is right-to-left.
+    pub rtl: bool,
+}
+
+/// How the lines of a [`TextLayout`] are placed horizontally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    /// Left for left-to-right paragraphs, right for right-to-left ones.
+    #[default]
+    Start,
+    End,
+    Left,
+    Center,
+    Right,
+    /// Stretches the spaces so that every line but the last of each paragraph fills the max width.
+    Justify,
+}
+
+/// Where the lines of a [`TextLayout`] are placed in its max height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
+
+/// How big a text is, from [`measure_text`] or [`TextLayout::measure`].
+#[derive(Debug, Clone)]
+pub struct TextMetrics {
+    pub width: f64,
+    /// From the top of the first line to the bottom of the last one.
+    pub height: f64,
+    /// How far the first line goes above its baseline.
+    pub ascent: f64,
+    /// How far the last line goes below its baseline.
+    pub descent: f64,
+    pub line_count: usize,
+    /// Every glyph, in the order they're drawn, placed relative to the first baseline.
+    pub glyphs: Vec<Glyph>,
+}
+
+/// A text turned into glyphs, ready to be drawn with `fill_layout`.
+///
+/// The first font is used for everything it can draw, and the next ones are fallbacks for the rest, like emojis or other scripts. Each line of the text (separated by `\n`) is laid out on its own, and right-to-left parts are reordered.
+///
+/// The text is only shaped once: the `with_*` methods just place the lines again.
+#[derive(Debug, Clone)]
+pub struct TextLayout {
+    pub lines: Vec<TextLine>,
+    /// The box of the text, from the top of the first line, the baseline of the first line being at 0 when aligned to the top. It's as big as the max width and height when they're set.
+    pub bounds: Rect,
+    text: String,
+    fonts: Vec<Font>,
+    size: f64,
+    faces: Vec<Face>,
+    styles: Vec<ResolvedStyle>,
+    spans: Vec<(Range<usize>, usize)>,
+    clusters: Vec<ShapedCluster>,
+    paragraphs: Vec<Paragraph>,
+    max_width: Option<f64>,
+    max_height: Option<f64>,
+    max_lines: Option<usize>,
+    align: TextAlign,
+    vertical_align: VerticalAlign,
+    line_spacing: f64,
+    ellipsis: Option<Vec<ShapedCluster>>,
+    truncated: bool,
+}
+
+/// A font, with the variations it's used with.
+#[derive(Debug, Clone, PartialEq)]
+struct Face {
+    font: usize,
+    coords: Vec<i16>,
+    synthetic_bold: bool,
+}
+
+/// A [`TextStyle`] with the faces it's shaped with, the first one being its main font and the others its fallbacks.
+#[derive(Debug, Clone)]
+struct ResolvedStyle {
+    faces: Vec<usize>,
+    size: f32,
+    color: Option<Color>,
+    underline: bool,
+    strikethrough: bool,
+    metrics: LineMetrics,
+}
+
+/// Characters shaped together, that can't be split across lines.
+#[derive(Debug, Clone)]
+struct ShapedCluster {
+    range: Range<usize>,
+    face: usize,
+    style: usize,
+    level: u8,
+    glyphs: Vec<ShapedGlyph>,
+    whitespace: bool,
+    boundary: Boundary,
+}
+
+impl ShapedCluster {
+    fn advance(&self) -> f32 {
+        self.glyphs.iter().map(|g| g.advance).sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Paragraph {
+    range: Range<usize>,
+    clusters: Range<usize>,
+    level: u8,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct LineMetrics {
+    ascent: f64,
+    descent: f64,
+    leading: f64,
+    underline_offset: f64,
+    strikeout_offset: f64,
+    stroke_size: f64,
+}
+
+impl LineMetrics {
+    fn new(font: &FontRef, coords: &[i16], size: f32) -> Self {
+        let m = font.metrics(coords).scale(size);
+
+        Self {
+            ascent: m.ascent as f64,
+            descent: m.descent as f64,
+            leading: m.leading as f64,
+            underline_offset: m.underline_offset as f64,
+            strikeout_offset: m.strikeout_offset as f64,
+            stroke_size: m.stroke_size as f64,
+        }
+    }
+
+    fn height(&self) -> f64 {
+        self.ascent + self.descent + self.leading
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self {
+            ascent: self.ascent.max(other.ascent),
+            descent: self.descent.max(other.descent),
+            leading: self.leading.max(other.leading),
+            ..self
+        }
+    }
+}
+
+/// What shaping needs from a layout.
+struct Shaping<'a> {
+    fonts: Vec<FontRef<'a>>,
+    faces: &'a [Face],
+    styles: &'a [ResolvedStyle],
+}
+
+impl TextLayout {
+    pub fn new(text: &str, fonts: &[Font], size: f64) -> Self {
+        Self::rich(&RichText::from(text), fonts, size)
+    }
+
+    /// Lays out a text with a single style, like the axes of a variable font: `TextStyle::default().with_weight(600.0).with_width(80.0)`.
+    pub fn styled(text: &str, fonts: &[Font], size: f64, style: TextStyle) -> Self {
+        Self::rich(&RichText::new().with_span(text, style), fonts, size)
+    }
+
+    /// Lays out a text whose spans have their own styles. The spans that don't set a font or a size use `fonts` and `size`, and `fonts` are the fallbacks of every span.
+    pub fn rich(text: &RichText, fonts: &[Font], size: f64) -> Self {
+        let fonts: Vec<Font> = fonts
+            .iter()
+            .filter(|f| font_ref(f).is_some())
+            .cloned()
+            .collect();
+        let layout_fonts = fonts.len();
+
+        let mut layout = Self {
+            lines: vec![],
+            bounds: Rect::ZERO,
+            text: text.to_string(),
+            fonts,
+            size,
+            faces: vec![],
+            styles: vec![],
+            spans: vec![],
+            clusters: vec![],
+            paragraphs: vec![],
+            max_width: None,
+            max_height: None,
+            max_lines: None,
+            align: TextAlign::default(),
+            vertical_align: VerticalAlign::default(),
+            line_spacing: 1.0,
+            ellipsis: None,
+            truncated: false,
+        };
+
+        // The first style is the one of the layout, used by empty lines and the ellipsis.
+        let mut sources = vec![TextStyle::default()];
+        layout.resolve_style(&TextStyle::default(), layout_fonts);
+
+        let mut start = 0;
+
+        for span in &text.spans {
+            let style = match sources.iter().position(|s| *s == span.style) {
+                Some(i) => i,
+                None => {
+                    sources.push(span.style.clone());
+                    layout.resolve_style(&span.style, layout_fonts)
+                }
+            };
+
+            layout.spans.push((start..start + span.text.len(), style));
+            start += span.text.len();
+        }
+
+        layout.shape();
+        layout.break_lines();
+        layout
+    }
+
+    /// Wraps the lines longer than `width` between words, or inside the words that don't fit on their own.
+    pub fn with_max_width(mut self, width: f64) -> Self {
+        self.max_width = Some(width);
+        self.break_lines();
+        self
+    }
+
+    /// Only keeps the lines that fit in `height`, and places them in it with the vertical alignment.
+    pub fn with_max_height(mut self, height: f64) -> Self {
+        self.max_height = Some(height);
+        self.break_lines();
+        self
+    }
+
+    pub fn with_max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines);
+        self.break_lines();
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self.break_lines();
+        self
+    }
+
+    pub fn with_vertical_align(mut self, align: VerticalAlign) -> Self {
+        self.vertical_align = align;
+        self.break_lines();
+        self
+    }
+
+    /// Multiplies the distance between the lines, `1.0` being the one of the font.
+    pub fn with_line_spacing(mut self, spacing: f64) -> Self {
+        self.line_spacing = spacing;
+        self.break_lines();
+        self
+    }
+
+    /// Ends the last line with "…" when the text doesn't fit in the max height or number of lines.
+    pub fn with_ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis.then(|| self.shape_ellipsis());
+        self.break_lines();
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    /// Whether some lines didn't fit and were left out.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Every run of glyphs, line after line.
+    pub fn runs(&self) -> impl Iterator<Item = &GlyphRun> {
+        self.lines.iter().flat_map(|l| l.runs.iter())
+    }
+
+    /// The size of the laid out text, and where its glyphs are.
+    pub fn measure(&self) -> TextMetrics {
+        TextMetrics {
+            width: self.bounds.width(),
+            height: self.bounds.height(),
+            ascent: self.lines.first().map_or(0.0, |l| l.ascent),
+            descent: self.lines.last().map_or(0.0, |l| l.descent),
+            line_count: self.lines.len(),
+            glyphs: self.runs().flat_map(|r| r.glyphs.iter().copied()).collect(),
+        }
+    }
+
+    /// Adds a style, with its own font in front of the first `layout_fonts`.
+    fn resolve_style(&mut self, style: &TextStyle, layout_fonts: usize) -> usize {
+        let size = style
+            .size
+            .filter(|s| s.is_finite() && *s > 0.0)
+            .unwrap_or(self.size) as f32;
+        let mut fonts = vec![];
+
+        if let Some(font) = style.font.as_ref().filter(|f| font_ref(f).is_some()) {
+            let i = match self.fonts.iter().position(|f| f == font) {
+                Some(i) => i,
+                None => {
+                    self.fonts.push(font.clone());
+                    self.fonts.len() - 1
+                }
+            };
+
+            fonts.push(i);
+        }
+
+        for i in 0..layout_fonts {
+            if !fonts.contains(&i) {
+                fonts.push(i);
+            }
+        }
+
+        let variations = style.axis_settings();
+        let bold = style.weight.is_some_and(|w| w >= 600.0);
+
+        let faces: Vec<usize> = fonts
+            .into_iter()
+            .map(|font| {
+                let font_ref = font_ref(&self.fonts[font]);
+                let coords = font_ref
+                    .map(|f| f.variations().normalized_coords(&variations).collect())
+                    .unwrap_or_default();
+                let synthetic_bold = bold
+                    && font_ref.is_some_and(|f| {
+                        f.variations()
+                            .find_by_tag(tag_from_bytes(b"wght"))
+                            .is_none()
+                    });
+
+                self.add_face(Face {
+                    font,
+                    coords,
+                    synthetic_bold,
+                })
+            })
+            .collect();
+
+        let metrics = faces
+            .first()
+            .and_then(|i| {
+                let face = &self.faces[*i];
+                let font = font_ref(&self.fonts[face.font])?;
+                Some(LineMetrics::new(&font, &face.coords, size))
+            })
+            .unwrap_or_default();
+
+        self.styles.push(ResolvedStyle {
+            faces,
+            size,
+            color: style.color,
+            underline: style.underline,
+            strikethrough: style.strikethrough,
+            metrics,
+        });
+
+        self.styles.len() - 1
+    }
+
+    fn add_face(&mut self, face: Face) -> usize {
+        match self.faces.iter().position(|f| *f == face) {
+            Some(i) => i,
+            None => {
+                self.faces.push(face);
+                self.faces.len() - 1
+            }
+        }
+    }
+
+    fn shape(&mut self) {
+        let shaping = Shaping {
+            fonts: self.fonts.iter().filter_map(font_ref).collect(),
+            faces: &self.faces,
+            styles: &self.styles,
+        };
+
+        let mut start = 0;
+        // The spans are in the order of the text, so they're walked along with it.
+        let mut span = 0;
+
+        for part in self.text.split('\n') {
+            let range = start..start + part.len();
+            let first = self.clusters.len();
+
+            let styles: Vec<usize> = part
+                .char_indices()
+                .map(|(i, _)| {
+                    let position = start + i;
+
+                    while self.spans.get(span).is_some_and(|(r, _)| r.end <= position) {
+                        span += 1;
+                    }
+
+                    self.spans
+                        .get(span)
+                        .filter(|(r, _)| r.contains(&position))
+                        .map_or(0, |(_, s)| *s)
+                })
+                .collect();
+
+            let level = shape_paragraph(part, start, &styles, &shaping, &mut self.clusters);
+
+            self.paragraphs.push(Paragraph {
+                range,
+                clusters: first..self.clusters.len(),
+                level,
+            });
+
+            start += part.len() + 1;
+        }
+    }
+
+    /// Shapes the ellipsis, with three dots if the fonts don't have the "…" character.
+    fn shape_ellipsis(&self) -> Vec<ShapedCluster> {
+        let shaping = Shaping {
+            fonts: self.fonts.iter().filter_map(font_ref).collect(),
+            faces: &self.faces,
+            styles: &self.styles,
+        };
+
+        let mut clusters = vec![];
+
+        for ellipsis in ["\u{2026}", "..."] {
+            clusters.clear();
+
+            let styles = vec![0; ellipsis.chars().count()];
+            shape_paragraph(ellipsis, 0, &styles, &shaping, &mut clusters);
+
+            if clusters.iter().flat_map(|c| &c.glyphs).all(|g| g.id != 0) {
+                break;
+            }
+        }
+
+        clusters
+    }
+
+    fn break_lines(&mut self) {
+        self.lines.clear();
+        self.truncated = false;
+
+        let mut breaks: Vec<(usize, Range<usize>)> = vec![];
+
+        for (i, paragraph) in self.paragraphs.iter().enumerate() {
+            for range in self.wrap(paragraph.clusters.clone()) {
+                breaks.push((i, range));
+            }
+        }
+
+        // The lines that fit, with their metrics and baselines from the top of the text.
+        let mut kept: Vec<(usize, Range<usize>, LineMetrics, f64)> = vec![];
+        let mut top = 0.0;
+
+        for (p, range) in breaks {
+            let metrics = self.line_metrics(&range);
+            let bottom = top + metrics.ascent + metrics.descent;
+
+            let too_many = self.max_lines.is_some_and(|max| kept.len() >= max);
+            let too_high = self.max_height.is_some_and(|max| bottom > max) && !kept.is_empty();
+
+            if too_many || too_high {
+                self.truncated = true;
+                break;
+            }
+
+            kept.push((p, range, metrics, top + metrics.ascent));
+            top += metrics.height() * self.line_spacing;
+        }
+
+        let first_ascent = kept.first().map_or(0.0, |k| k.2.ascent);
+
+        for (i, (p, range, metrics, baseline)) in kept.iter().enumerate() {
+            let paragraph = &self.paragraphs[*p];
+            let ellipsis = if self.truncated && i + 1 == kept.len() {
+                self.ellipsis.as_deref()
+            } else {
+                None
+            };
+            let justify = self.align == TextAlign::Justify
+                && ellipsis.is_none()
+                && range.end != paragraph.clusters.end;
+
+            let mut line = self.layout_line(
+                paragraph,
+                range.clone(),
+                baseline - first_ascent,
+                ellipsis,
+                justify,
+            );
+            line.ascent = metrics.ascent;
+            line.descent = metrics.descent;
+            self.lines.push(line);
+        }
+
+        self.align_lines();
+    }
+
+    /// The biggest metrics of the styles in a line, or the ones of the layout for empty lines.
+    fn line_metrics(&self, clusters: &Range<usize>) -> LineMetrics {
+        if clusters.is_empty() {
+            return self.styles[0].metrics;
+        }
+
+        self.clusters[clusters.clone()]
+            .iter()
+            .map(|c| self.styles[c.style].metrics)
+            .fold(LineMetrics::default(), LineMetrics::max)
+    }
+
+    /// Splits the clusters of a paragraph into lines that fit in the max width.
+    fn wrap(&self, clusters: Range<usize>) -> Vec<Range<usize>> {
+        let Some(max_width) = self.max_width else {
+            return vec![clusters];
+        };
+
+        let mut lines = vec![];
+        let mut start = clusters.start;
+        let mut width = 0.0;
+        let mut last_break = None;
+
+        for i in clusters.clone() {
+            let cluster = &self.clusters[i];
+            let advance = cluster.advance() as f64;
+
+            if i > start && cluster.boundary >= Boundary::Line {
+                last_break = Some(i);
+            }
+
+            // Spaces can go past the end of a line, they aren't drawn there.
+            if !cluster.whitespace && i > start && width + advance > max_width {
+                let end = last_break.take().unwrap_or(i);
+
+                lines.push(start..end);
+                start = end;
+                width = self.clusters[start..i]
+                    .iter()
+                    .map(|c| c.advance() as f64)
+                    .sum();
+            }
+
+            width += advance;
+        }
+
+        lines.push(start..clusters.end);
+        lines
+    }
+
+    /// Places the clusters of a line, reordering the right-to-left ones.
+    fn layout_line(
+        &self,
+        paragraph: &Paragraph,
+        clusters: Range<usize>,
+        baseline: f64,
+        ellipsis: Option<&[ShapedCluster]>,
+        justify: bool,
+    ) -> TextLine {
+        let mut clusters: Vec<&ShapedCluster> = self.clusters[clusters].iter().collect();
+
+        let range = match (clusters.first(), clusters.last()) {
+            (Some(first), Some(last)) => first.range.start..last.range.end,
+            _ => paragraph.range.clone(),
+        };
+
+        let ellipsis_width: f32 = ellipsis
+            .iter()
+            .flat_map(|e| e.iter())
+            .map(|c| c.advance())
+            .sum();
+        let max_width = self.max_width.unwrap_or(f64::INFINITY) as f32;
+
+        // Spaces at the end of the line aren't drawn, and some more clusters make room for the ellipsis.
+        while clusters.last().is_some_and(|c| {
+            let width: f32 = clusters.iter().map(|c| c.advance()).sum();
+            c.whitespace || (ellipsis.is_some() && width + ellipsis_width > max_width)
+        }) {
+            clusters.pop();
+        }
+
+        let mut levels: Vec<u8> = clusters.iter().map(|c| c.level).collect();
+
+        for cluster in ellipsis.into_iter().flatten() {
+            clusters.push(cluster);
+            levels.push(paragraph.level);
+        }
+
+        let width: f32 = clusters.iter().map(|c| c.advance()).sum();
+        let spaces = clusters.iter().filter(|c| c.whitespace).count();
+        let extra = if justify && spaces > 0 && width < max_width {
+            (max_width - width) / spaces as f32
+        } else {
+            0.0
+        };
+
+        let mut runs: Vec<GlyphRun> = vec![];
+        let mut decorations: Vec<TextDecoration> = vec![];
+        let mut current = None;
+        let mut pen_x = 0f32;
+
+        for i in visual_order(&levels) {
+            let cluster = clusters[i];
+            let style = &self.styles[cluster.style];
+            let face = &self.faces[cluster.face];
+            let rtl = levels[i] % 2 == 1;
+
+            if current != Some((cluster.face, cluster.style, rtl)) {
+                current = Some((cluster.face, cluster.style, rtl));
+                runs.push(GlyphRun {
+                    font: self.fonts[face.font].clone(),
+                    font_size: style.size,
+                    normalized_coords: face
+                        .coords
+                        .iter()
+                        .map(|c| NormalizedCoord::from_bits(*c))
+                        .collect(),
+                    glyphs: vec![],
+                    rtl,
+                    color: style.color,
+                    synthetic_bold: face.synthetic_bold,
+                });
+            }
+
+            let run = runs.last_mut().unwrap();
+            let start = pen_x;
+
+            for glyph in &cluster.glyphs {
+                run.glyphs.push(Glyph {
+                    id: glyph.id as u32,
+                    x: pen_x + glyph.x,
+                    y: baseline as f32 - glyph.y,
+                });
+                pen_x += glyph.advance;
+            }
+
+            if cluster.whitespace {
+                pen_x += extra;
+            }
+
+            let m = &style.metrics;
+            let offsets = [
+                (style.underline, m.underline_offset),
+                (style.strikethrough, m.strikeout_offset),
+            ];
+
+            for (_, offset) in offsets.iter().filter(|o| o.0) {
+                let y = baseline - offset;
+                let rect = Rect::new(
+                    start as f64,
+                    y - m.stroke_size / 2.0,
+                    pen_x as f64,
+                    y + m.stroke_size / 2.0,
+                );
+
+                add_decoration(&mut decorations, rect, style.color);
+            }
+        }
+
+        TextLine {
+            range,
+            runs,
+            decorations,
+            x: 0.0,
+            width: pen_x as f64,
+            baseline,
+            ascent: 0.0,
+            descent: 0.0,
+            rtl: paragraph.level % 2 == 1,
+        }
+    }
+
+    /// Moves the lines to their alignment, and sets the bounds of the layout.
+    fn align_lines(&mut self) {
+        let width = self
+            .max_width
+            .unwrap_or_else(|| self.lines.iter().map(|l| l.width).fold(0.0, f64::max));
+        let top = self
+            .lines
+            .first()
+            .map_or(self.styles[0].metrics.ascent, |l| l.ascent);
+        let content = self
+            .lines
+            .last()
+            .map_or(0.0, |l| l.baseline + l.descent + top);
+        let height = self.max_height.unwrap_or(content);
+
+        let dy = match self.vertical_align {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Center => (height - content) / 2.0,
+            VerticalAlign::Bottom => height - content,
+        };
+
+        for line in &mut self.lines {
+            let free = width - line.width;
+            let dx = match self.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => free / 2.0,
+                TextAlign::Right => free,
+                TextAlign::Start | TextAlign::Justify if line.rtl => free,
+                TextAlign::Start | TextAlign::Justify => 0.0,
+                TextAlign::End if line.rtl => 0.0,
+                TextAlign::End => free,
+            };
+
+            line.x = dx;
+            line.baseline += dy;
+
+            for glyph in line.runs.iter_mut().flat_map(|r| r.glyphs.iter_mut()) {
+                glyph.x += dx as f32;
+                glyph.y += dy as f32;
+            }
+
+            for decoration in &mut line.decorations {
+                decoration.rect = decoration.rect + Vec2::new(dx, dy);
+            }
+        }
+
+        self.bounds = Rect::new(0.0, -top, width, height - top);
+    }
+}
+
+/// Measures a text drawn with `fill_text`, without drawing it.
+///
+/// `fill_text` places the first baseline `size` pixels under its transform, so the text covers from `size - ascent` to `size - ascent + height` vertically.
+pub fn measure_text(font: &Font, text: &str, size: f64) -> TextMetrics {
+    TextLayout::new(text, std::slice::from_ref(font), size).measure()
+}
+
+fn font_ref(font: &Font) -> Option<FontRef<'_>> {
+    FontRef::from_index(font.data.as_ref(), font.index as usize)
+}
+
+/// Adds an underline or a strikethrough, extending the previous one if it continues it.
+fn add_decoration(decorations: &mut Vec<TextDecoration>, rect: Rect, color: Option<Color>) {
+    let previous = decorations.iter_mut().rev().find(|d| {
+        d.color == color
+            && d.rect.y0 == rect.y0
+            && d.rect.y1 == rect.y1
+            && (d.rect.x1 - rect.x0).abs() < 0.01
+    });
+
+    match previous {
+        Some(d) => d.rect.x1 = rect.x1,
+        None => decorations.push(TextDecoration { rect, color }),
+    }
+}
+
+/// Characters of a single direction, script and style.
+#[derive(Clone, Copy)]
+struct Item {
+    script: Script,
+    level: u8,
+    style: usize,
+}
+
+/// Shapes a line of text, split into items. Returns the direction level of the line.
+fn shape_paragraph(
+    text: &str,
+    offset: usize,
+    styles: &[usize],
+    shaping: &Shaping,
+    out: &mut Vec<ShapedCluster>,
+) -> u8 {
+    let bidi = ParagraphBidiInfo::new(text, None);
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let props: Vec<_> = analyze(text.chars()).collect();
+    let scripts = resolve_scripts(props.iter().map(|(p, _)| p.script()).collect());
+
+    let mut i = 0;
+
+    while i < chars.len() {
+        let item = Item {
+            script: scripts[i],
+            level: bidi.levels[chars[i].0].number(),
+            style: styles[i],
+        };
+        let mut end = i + 1;
+
+        while end < chars.len()
+            && bidi.levels[chars[end].0].number() == item.level
+            && scripts[end] == item.script
+            && styles[end] == item.style
+        {
+            end += 1;
+        }
+
+        let tokens = (i..end).map(|k| Token {
+            ch: chars[k].1,
+            offset: (offset + chars[k].0) as u32,
+            len: chars[k].1.len_utf8() as u8,
+            info: CharInfo::new(props[k].0, props[k].1),
+            data: 0,
+        });
+
+        shape_item(Parser::new(item.script, tokens), item, shaping, out);
+        i = end;
+    }
+
+    bidi.paragraph_level.number()
+}
+
+/// Shapes an item, switching faces when the current one doesn't have its characters.
+fn shape_item<I: Iterator<Item = Token> + Clone>(
+    mut parser: Parser<I>,
+    item: Item,
+    shaping: &Shaping,
+    out: &mut Vec<ShapedCluster>,
+) {
+    let faces = &shaping.styles[item.style].faces;
+
+    if faces.is_empty() {
+        return;
+    }
+
+    let mut cluster = CharCluster::new();
+    let mut group: Vec<CharCluster> = vec![];
+    let mut group_face = faces[0];
+
+    while parser.next(&mut cluster) {
+        let face = select_face(&mut cluster, faces, shaping);
+
+        if face != group_face && !group.is_empty() {
+            shape_clusters(&group, group_face, item, shaping, out);
+            group.clear();
+        }
+
+        group_face = face;
+        group.push(cluster);
+    }
+
+    if !group.is_empty() {
+        shape_clusters(&group, group_face, item, shaping, out);
+    }
+}
+
+/// Finds the first face that has every character of a cluster, or the one that has the most of them.
+fn select_face(cluster: &mut CharCluster, faces: &[usize], shaping: &Shaping) -> usize {
+    let mut best = faces[0];
+
+    for face in faces {
+        let charmap = shaping.fonts[shaping.faces[*face].font].charmap();
+
+        match cluster.map(|ch| charmap.map(ch)) {
+            Status::Complete => return *face,
+            Status::Keep => best = *face,
+            Status::Discard => {}
+        }
+    }
+
+    best
+}
+
+fn shape_clusters(
+    clusters: &[CharCluster],
+    face: usize,
+    item: Item,
+    shaping: &Shaping,
+    out: &mut Vec<ShapedCluster>,
+) {
+    let direction = if item.level % 2 == 1 {
+        Direction::RightToLeft
+    } else {
+        Direction::LeftToRight
+    };
+
+    let font = shaping.fonts[shaping.faces[face].font];
+
+    SHAPE_CONTEXT.with_borrow_mut(|context| {
+        let mut shaper = context
+            .builder(font)
+            .script(item.script)
+            .direction(direction)
+            .size(shaping.styles[item.style].size)
+            .normalized_coords(&shaping.faces[face].coords)
+            .build();
+
+        for cluster in clusters {
+            shaper.add_cluster(cluster);
+        }
+
+        shaper.shape_with(|c| {
+            out.push(ShapedCluster {
+                range: c.source.to_range(),
+                face,
+                style: item.style,
+                level: item.level,
+                glyphs: c.glyphs.to_vec(),
+                whitespace: c.info.is_whitespace(),
+                boundary: c.info.boundary(),
+            });
+        });
+    });
+}
+
+/// Gives the characters shared by every script (spaces, punctuation, marks...) the script of the characters around them.
+fn resolve_scripts(mut scripts: Vec<Script>) -> Vec<Script> {
+    let shared = |s: Script| matches!(s, Script::Common | Script::Inherited | Script::Unknown);
+    let first = scripts
+        .iter()
+        .copied()
+        .find(|s| !shared(*s))
+        .unwrap_or(Script::Latin);
+    let mut last = first;
+
+    for script in &mut scripts {
+        if shared(*script) {
+            *script = last;
+        } else {
+            last = *script;
+        }
+    }
+
+    scripts
+}
+
+/// The order in which the clusters of a line are drawn from left to right, reversing every part that has a higher level than what's around it.
+fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let highest = levels.iter().copied().max().unwrap_or(0);
+
+    let Some(lowest_odd) = levels.iter().copied().filter(|l| l % 2 == 1).min() else {
+        return order;
+    };
+
+    for level in (lowest_odd..=highest).rev() {
+        let mut i = 0;
+
+        while i < order.len() {
+            if levels[order[i]] < level {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+
+            while i < order.len() && levels[order[i]] >= level {
+                i += 1;
+            }
+
+            order[start..i].reverse();
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use vello::peniko::Blob;
+
+    const TEXT: &str =
+        "The quick brown fox jumps over the lazy dog.\nSupercalifragilisticexpialidocious";
+
+    fn fira() -> Vec<Font> {
+        let data = include_bytes!("../examples/assets/FiraSans-Regular.ttf");
+        vec![Font::new(Blob::new(Arc::new(data.to_vec())), 0)]
+    }
+
+    fn line_texts(layout: &TextLayout) -> Vec<&str> {
+        layout
+            .lines
+            .iter()
+            .map(|l| &layout.text()[l.range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn wraps_between_words() {
+        let layout = TextLayout::new(TEXT, &fira(), 20.0).with_max_width(150.0);
+        let lines = line_texts(&layout);
+
+        assert_eq!(
+            &lines[..3],
+            ["The quick brown ", "fox jumps over ", "the lazy dog."]
+        );
+        // A word longer than the max width is split where it has to.
+        assert_eq!(lines[3..].concat(), "Supercalifragilisticexpialidocious");
+        assert!(lines.len() > 4);
+        assert!(layout.lines.iter().all(|l| l.width <= 150.0));
+        assert!(layout
+            .lines
+            .windows(2)
+            .all(|l| l[1].baseline > l[0].baseline));
+        assert!(!layout.is_truncated());
+    }
+
+    #[test]
+    fn justifies_all_lines_but_the_last_of_each_paragraph() {
+        let layout = TextLayout::new(TEXT, &fira(), 20.0)
+            .with_max_width(150.0)
+            .with_align(TextAlign::Justify);
+        let widths: Vec<f64> = layout.lines.iter().map(|l| l.width).collect();
+
+        assert!((widths[0] - 150.0).abs() < 0.01);
+        assert!((widths[1] - 150.0).abs() < 0.01);
+        assert!(widths[2] < 149.0);
+        assert!(layout.lines.iter().all(|l| l.x == 0.0));
+    }
+
+    #[test]
+    fn ellipsis_replaces_the_end_of_the_last_line() {
+        let fonts = fira();
+        let ellipsis = TextLayout::new("\u{2026}", &fonts, 20.0)
+            .runs()
+            .next()
+            .unwrap()
+            .glyphs[0]
+            .id;
+        let layout = TextLayout::new(TEXT, &fonts, 20.0)
+            .with_max_width(150.0)
+            .with_max_height(60.0)
+            .with_ellipsis(true);
+
+        assert!(layout.is_truncated());
+        assert_eq!(layout.lines.len(), 2);
+
+        let last = layout.lines.last().unwrap();
+        let last_glyph = last.runs.last().unwrap().glyphs.last().unwrap();
+
+        assert_eq!(last_glyph.id, ellipsis);
+        assert!(last.width <= 150.0);
+    }
+
+    #[test]
+    fn spans_across_lines() {
+        let text = RichText::new()
+            .with_span("ab\nc", TextStyle::default().with_size(30.0))
+            .with_span("", TextStyle::default().with_size(50.0))
+            .with_span("d\ne", TextStyle::default());
+        let layout = TextLayout::rich(&text, &fira(), 20.0);
+        let sizes: Vec<Vec<f32>> = layout
+            .lines
+            .iter()
+            .map(|l| l.runs.iter().map(|r| r.font_size).collect())
+            .collect();
+
+        assert_eq!(sizes, [vec![30.0], vec![30.0, 20.0], vec![20.0]]);
+    }
+
+    #[test]
+    fn max_lines_without_ellipsis() {
+        let layout = TextLayout::new(TEXT, &fira(), 20.0)
+            .with_max_width(150.0)
+            .with_max_lines(1);
+
+        assert!(layout.is_truncated());
+        assert_eq!(line_texts(&layout), ["The quick brown "]);
+    }
+
+    #[test]
+    fn bold_without_weight_axis_is_synthetic() {
+        let text = RichText::parse("<b>bold</b> regular").unwrap();
+        let layout = TextLayout::rich(&text, &fira(), 20.0);
+        let bold: Vec<bool> = layout.runs().map(|r| r.synthetic_bold).collect();
+
+        assert_eq!(bold, [true, false]);
+    }
+
+    #[test]
+    fn visual_order_of_mixed_levels() {
+        assert_eq!(visual_order(&[0, 0, 0]), [0, 1, 2]);
+        assert_eq!(visual_order(&[0, 0, 1, 1, 1, 0]), [0, 1, 4, 3, 2, 5]);
+        // A left-to-right part inside a right-to-left one keeps its own order.
+        assert_eq!(visual_order(&[1, 1, 2, 2, 1]), [4, 2, 3, 1, 0]);
+        assert_eq!(visual_order(&[0, 1, 1, 2, 2, 1, 0]), [0, 5, 3, 4, 2, 1, 6]);
+    }
+
+    #[test]
+    fn shared_scripts_follow_their_neighbours() {
+        use Script::*;
+
+        assert_eq!(
+            resolve_scripts(vec![Common, Latin, Common, Arabic, Inherited, Common]),
+            [Latin, Latin, Latin, Arabic, Arabic, Arabic]
+        );
+        assert_eq!(resolve_scripts(vec![Common, Unknown]), [Latin, Latin]);
+    }
+
+    #[test]
+    fn right_to_left_line_reverses_its_clusters() {
+        let fonts = fira();
+        let ids = |text: &str| -> Vec<u32> {
+            TextLayout::new(text, &fonts, 20.0)
+                .runs()
+                .flat_map(|r| r.glyphs.iter().map(|g| g.id))
+                .collect()
+        };
+
+        // The right-to-left marks make the paragraph right-to-left, and put the words in opposite order.
+        let layout = TextLayout::new("\u{200F}ab\u{200F} cd", &fonts, 20.0);
+        assert!(layout.lines[0].rtl);
+        assert_eq!(ids("\u{200F}ab\u{200F} cd"), ids("cd ab"));
+
+        // A right-to-left override reverses the letters themselves.
+        assert_eq!(ids("\u{202E}abc"), ids("cba"));
+    }
+}
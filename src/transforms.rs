@@ -1,19 +1,213 @@
 //! Everything related to translation, rotation, scaling and any kind of transformation.
 
 use bevy_ecs::prelude::*;
+use bevy_ecs::query::{QueryData, QueryFilter, ROQueryItem};
 
-use vello::peniko::kurbo::Affine;
+use crate::hierarchy::{propagate_hierarchy, Children, HierarchyRoot};
+
+use vello::peniko::kurbo::{Affine, Vec2};
+
+use std::f64::consts::{PI, TAU};
 
 /// Describes the position, rotation, scale and any kind of transformation of an entity. This is a translation layer between Bella and [`vello::kurbo`]'s [`Affine`].
-#[derive(Component)]
+///
+/// The [`Affine`] can also be read and written as separate translation, rotation, scale and skew, applied in the opposite order (scale first, translation last).
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
 pub struct Transform {
     pub affine: Affine,
 }
 
+/// A [`Transform`] split into its components.
+///
+/// - `rotation` and `skew` are in radians.
+/// - `skew` slants the Y axis of the entity towards its X axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformComponents {
+    pub translation: Vec2,
+    pub rotation: f64,
+    pub scale: Vec2,
+    pub skew: f64,
+}
+
+impl Default for TransformComponents {
+    fn default() -> Self {
+        Self {
+            translation: Vec2::ZERO,
+            rotation: 0.0,
+            scale: Vec2::new(1.0, 1.0),
+            skew: 0.0,
+        }
+    }
+}
+
+impl TransformComponents {
+    pub fn to_affine(&self) -> Affine {
+        Affine::translate(self.translation)
+            * Affine::rotate(self.rotation)
+            * Affine::new([1.0, 0.0, self.skew.tan(), 1.0, 0.0, 0.0])
+            * Affine::scale_non_uniform(self.scale.x, self.scale.y)
+    }
+
+    pub fn from_affine(affine: Affine) -> Self {
+        let [a, b, c, d, e, f] = affine.as_coeffs();
+
+        let scale_x = a.hypot(b);
+
+        if scale_x == 0.0 {
+            return Self {
+                translation: Vec2::new(e, f),
+                rotation: 0.0,
+                scale: Vec2::new(0.0, d),
+                skew: 0.0,
+            };
+        }
+
+        let rotation = b.atan2(a);
+        let (sin, cos) = rotation.sin_cos();
+        let scale_y = d * cos - c * sin;
+        let skew = if scale_y == 0.0 {
+            0.0
+        } else {
+            ((c * cos + d * sin) / scale_y).atan()
+        };
+
+        Self {
+            translation: Vec2::new(e, f),
+            rotation,
+            scale: Vec2::new(scale_x, scale_y),
+            skew,
+        }
+    }
+}
+
 impl Transform {
     pub fn new(a: Affine) -> Self {
         Self { affine: a }
     }
+
+    pub fn from_translation(translation: Vec2) -> Self {
+        Self::new(Affine::translate(translation))
+    }
+
+    pub fn from_rotation(rotation: f64) -> Self {
+        Self::new(Affine::rotate(rotation))
+    }
+
+    pub fn from_scale(scale: Vec2) -> Self {
+        Self::new(Affine::scale_non_uniform(scale.x, scale.y))
+    }
+
+    /// Creates a [`Transform`] from a translation, a rotation (in radians) and a scale.
+    pub fn from_trs(translation: Vec2, rotation: f64, scale: Vec2) -> Self {
+        Self::from_components(TransformComponents {
+            translation,
+            rotation,
+            scale,
+            skew: 0.0,
+        })
+    }
+
+    pub fn from_components(components: TransformComponents) -> Self {
+        Self::new(components.to_affine())
+    }
+
+    pub fn components(&self) -> TransformComponents {
+        TransformComponents::from_affine(self.affine)
+    }
+
+    pub fn set_components(&mut self, components: TransformComponents) {
+        self.affine = components.to_affine();
+    }
+
+    pub fn translation(&self) -> Vec2 {
+        self.affine.translation()
+    }
+
+    pub fn set_translation(&mut self, translation: Vec2) {
+        self.affine = self.affine.with_translation(translation);
+    }
+
+    /// Moves the entity by `offset`, in world space.
+    pub fn translate(&mut self, offset: Vec2) {
+        self.affine = self.affine.then_translate(offset);
+    }
+
+    /// The rotation of the entity, in radians.
+    pub fn rotation(&self) -> f64 {
+        self.components().rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: f64) {
+        let mut c = self.components();
+        c.rotation = rotation;
+        self.set_components(c);
+    }
+
+    /// Rotates the entity around its own position.
+    pub fn rotate(&mut self, angle: f64) {
+        self.rotate_around(self.translation(), angle);
+    }
+
+    /// Rotates the entity around a point of the world, moving it along.
+    pub fn rotate_around(&mut self, point: Vec2, angle: f64) {
+        self.affine = self.affine.then_rotate_about(angle, point.to_point());
+    }
+
+    pub fn scale(&self) -> Vec2 {
+        self.components().scale
+    }
+
+    pub fn set_scale(&mut self, scale: Vec2) {
+        let mut c = self.components();
+        c.scale = scale;
+        self.set_components(c);
+    }
+
+    /// The skew of the entity, in radians.
+    pub fn skew(&self) -> f64 {
+        self.components().skew
+    }
+
+    pub fn set_skew(&mut self, skew: f64) {
+        let mut c = self.components();
+        c.skew = skew;
+        self.set_components(c);
+    }
+
+    /// Rotates the entity so its X axis points towards `target`.
+    pub fn look_at(&mut self, target: Vec2) {
+        let direction = target - self.translation();
+
+        if direction != Vec2::ZERO {
+            self.set_rotation(direction.atan2());
+        }
+    }
+
+    /// Returns this [`Transform`] rotated so its X axis points towards `target`.
+    pub fn looking_at(mut self, target: Vec2) -> Self {
+        self.look_at(target);
+        self
+    }
+
+    /// Interpolates between two transforms, component by component. The rotation takes the shortest way around.
+    pub fn lerp(&self, other: &Transform, t: f64) -> Transform {
+        let a = self.components();
+        let b = other.components();
+
+        let mut rotation_delta = (b.rotation - a.rotation) % TAU;
+        if rotation_delta > PI {
+            rotation_delta -= TAU;
+        } else if rotation_delta < -PI {
+            rotation_delta += TAU;
+        }
+
+        Transform::from_components(TransformComponents {
+            translation: a.translation.lerp(b.translation, t),
+            rotation: a.rotation + rotation_delta * t,
+            scale: a.scale.lerp(b.scale, t),
+            skew: a.skew + (b.skew - a.skew) * t,
+        })
+    }
 }
 
 impl Default for Transform {
@@ -21,3 +215,183 @@ impl Default for Transform {
         Self::new(Affine::IDENTITY)
     }
 }
+
+impl From<Affine> for Transform {
+    fn from(affine: Affine) -> Self {
+        Self::new(affine)
+    }
+}
+
+impl From<Transform> for Affine {
+    fn from(transform: Transform) -> Self {
+        transform.affine
+    }
+}
+
+impl From<TransformComponents> for Transform {
+    fn from(components: TransformComponents) -> Self {
+        Self::from_components(components)
+    }
+}
+
+/// The final transformation of an entity, after applying the ones of all of its parents. Calculated every frame by [`propagate_transforms`].
+///
+/// Use this instead of [`Transform`] when drawing entities that might have a [`Parent`](crate::hierarchy::Parent).
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GlobalTransform {
+    pub affine: Affine,
+}
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self {
+            affine: Affine::IDENTITY,
+        }
+    }
+}
+
+/// Calculates the [`GlobalTransform`] of every entity with a [`Transform`], going from the entities without a [`Parent`](crate::hierarchy::Parent) down to their children.
+///
+/// A parent without a [`Transform`] counts as the identity, so its children are still placed relative to its own parent.
+pub fn propagate_transforms(
+    mut commands: Commands,
+    roots: Query<Entity, HierarchyRoot<Transform>>,
+    nodes: Query<(Option<&Transform>, Option<&Children>)>,
+    mut globals: Query<&mut GlobalTransform>,
+) {
+    propagate_hierarchy(
+        &roots,
+        &nodes,
+        Affine::IDENTITY,
+        |entity, transform, parent| {
+            let Some(transform) = transform else {
+                return parent;
+            };
+
+            let affine = parent * transform.affine;
+            set_global(&mut commands, &mut globals, entity, affine);
+            affine
+        },
+    );
+}
+
+fn set_global(
+    commands: &mut Commands,
+    globals: &mut Query<&mut GlobalTransform>,
+    entity: Entity,
+    affine: Affine,
+) {
+    match globals.get_mut(entity) {
+        Ok(mut g) => g.affine = affine,
+        Err(_) => {
+            commands.entity(entity).insert(GlobalTransform { affine });
+        }
+    }
+}
+
+/// The drawing order of an entity inside of its scene. Entities with a higher [`ZIndex`] are drawn on top, and the ones without it count as `ZIndex(0)`.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZIndex(pub i32);
+
+/// Extends [`Query`] with a way to iterate over its entities back-to-front, sorted by their [`ZIndex`].
+///
+/// The z-indices are read through `z_indices`, so the query itself doesn't need to include them.
+pub trait ZSortedQuery<D: QueryData> {
+    fn iter_z_sorted<'a>(
+        &'a self,
+        z_indices: &Query<&ZIndex>,
+    ) -> impl Iterator<Item = ROQueryItem<'a, D>>;
+}
+
+impl<D: QueryData, F: QueryFilter> ZSortedQuery<D> for Query<'_, '_, D, F> {
+    fn iter_z_sorted<'a>(
+        &'a self,
+        z_indices: &Query<&ZIndex>,
+    ) -> impl Iterator<Item = ROQueryItem<'a, D>> {
+        self.iter()
+            .sort_by_key::<Entity, i32>(|e| z_indices.get(*e).map_or(0, |z| z.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hierarchy::Parent;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn assert_affine_eq(a: Affine, b: Affine) {
+        for (a, b) in a.as_coeffs().iter().zip(b.as_coeffs()) {
+            assert!((a - b).abs() < 1e-9, "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn components_round_trip() {
+        let components = TransformComponents {
+            translation: Vec2::new(10.0, -20.0),
+            rotation: 0.7,
+            scale: Vec2::new(2.0, 0.5),
+            skew: 0.3,
+        };
+        let back = TransformComponents::from_affine(components.to_affine());
+
+        assert!((back.translation - components.translation).hypot() < 1e-9);
+        assert!((back.rotation - components.rotation).abs() < 1e-9);
+        assert!((back.scale - components.scale).hypot() < 1e-9);
+        assert!((back.skew - components.skew).abs() < 1e-9);
+    }
+
+    #[test]
+    fn components_of_affine() {
+        let affine = Affine::translate((3.0, 4.0))
+            * Affine::rotate(-1.2)
+            * Affine::scale_non_uniform(-1.5, 3.0);
+
+        assert_affine_eq(TransformComponents::from_affine(affine).to_affine(), affine);
+        assert_eq!(
+            TransformComponents::from_affine(Affine::IDENTITY),
+            TransformComponents::default()
+        );
+    }
+
+    #[test]
+    fn z_sorted_without_z_index_in_query() {
+        let mut world = World::new();
+        world.spawn((Transform::from_translation(Vec2::new(3.0, 0.0)), ZIndex(3)));
+        world.spawn(Transform::from_translation(Vec2::ZERO));
+        world.spawn((
+            Transform::from_translation(Vec2::new(-1.0, 0.0)),
+            ZIndex(-1),
+        ));
+
+        let order = world.run_system_once(|q: Query<&Transform>, z: Query<&ZIndex>| {
+            q.iter_z_sorted(&z)
+                .map(|t| t.affine.translation().x)
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(order, vec![-1.0, 0.0, 3.0]);
+    }
+
+    #[test]
+    fn children_of_parent_without_transform() {
+        let mut world = World::new();
+        let root = world
+            .spawn(Transform::from_translation(Vec2::new(10.0, 0.0)))
+            .id();
+        let middle = world.spawn(Parent(root)).id();
+        let child = world
+            .spawn((Parent(middle), Transform::from_scale(Vec2::new(2.0, 2.0))))
+            .id();
+        world.entity_mut(root).insert(Children(vec![middle]));
+        world.entity_mut(middle).insert(Children(vec![child]));
+
+        world.run_system_once(propagate_transforms);
+
+        assert!(world.get::<GlobalTransform>(middle).is_none());
+        assert_affine_eq(
+            world.get::<GlobalTransform>(child).unwrap().affine,
+            Affine::translate((10.0, 0.0)) * Affine::scale(2.0),
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Drawing only a part of a path, for loading bars and "draw-on" effects.
+
+use bevy_ecs::prelude::Component;
+use vello::kurbo::{BezPath, ParamCurve, ParamCurveArclen, PathSeg, Point, Shape};
+
+/// How precise the lengths along a path are, in pixels.
+const ACCURACY: f64 = 1e-3;
+
+/// The part of a path that's stroked, as fractions of its length from `0.0` to `1.0`.
+///
+/// `offset` moves the whole part along the path, wrapping around its end, so animating it makes the stroke run along a closed shape. Every field can be animated, for example with the values of an `interpoli` timeline.
+///
+/// As a component, it trims the strokes of the entity. Fills are left untouched.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Trim {
+    pub start: f64,
+    pub end: f64,
+    pub offset: f64,
+}
+
+impl Default for Trim {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+impl Trim {
+    pub fn new(start: f64, end: f64) -> Self {
+        Self {
+            start,
+            end,
+            offset: 0.0,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Only keeps the first `progress` of a path, like a loading bar.
+    pub fn progress(progress: f64) -> Self {
+        Self::new(0.0, progress)
+    }
+
+    pub fn apply(&self, shape: &impl Shape) -> BezPath {
+        trim_path(shape, self.start, self.end, self.offset)
+    }
+}
+
+/// Cuts the part of a shape between `start` and `end`, moved by `offset`, measured in fractions of its length.
+///
+/// When the part covers the whole length, the shape is kept as it is, with its closed subpaths still closed.
+pub fn trim_path(shape: &impl Shape, start: f64, end: f64, offset: f64) -> BezPath {
+    if end.min(1.0) - start.max(0.0) >= 1.0 {
+        return shape.to_path(0.1);
+    }
+
+    let segments: Vec<PathSeg> = shape.path_segments(0.1).collect();
+    let lengths: Vec<f64> = segments.iter().map(|s| s.arclen(ACCURACY)).collect();
+    let total: f64 = lengths.iter().sum();
+
+    let mut path = BezPath::new();
+    let (start, end) = (start.clamp(0.0, 1.0), end.clamp(0.0, 1.0));
+
+    if total <= 0.0 || end <= start {
+        return path;
+    }
+
+    let from = (start + offset).rem_euclid(1.0);
+    let to = from + (end - start);
+
+    if to <= 1.0 {
+        append_range(&mut path, &segments, &lengths, from * total, to * total);
+    } else {
+        // Goes past the end, so it continues from the start.
+        append_range(&mut path, &segments, &lengths, from * total, total);
+        append_range(&mut path, &segments, &lengths, 0.0, (to - 1.0) * total);
+    }
+
+    path
+}
+
+/// Appends the segments between the lengths `from` and `to` of the path.
+fn append_range(path: &mut BezPath, segments: &[PathSeg], lengths: &[f64], from: f64, to: f64) {
+    let mut position = 0.0;
+    let mut last: Option<Point> = path.segments().last().map(|s| s.end());
+
+    for (segment, length) in segments.iter().zip(lengths) {
+        let (segment_start, segment_end) = (position, position + length);
+        position = segment_end;
+
+        if *length <= 0.0 || segment_end <= from || segment_start >= to {
+            continue;
+        }
+
+        let t0 = if from > segment_start {
+            segment.inv_arclen(from - segment_start, ACCURACY)
+        } else {
+            0.0
+        };
+        let t1 = if to < segment_end {
+            segment.inv_arclen(to - segment_start, ACCURACY)
+        } else {
+            1.0
+        };
+
+        let part = segment.subsegment(t0..t1);
+
+        if last != Some(part.start()) {
+            path.move_to(part.start());
+        }
+
+        path.push(part.as_path_el());
+        last = Some(part.end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vello::kurbo::{PathEl, Rect};
+
+    const SQUARE: Rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+    fn length(path: &BezPath) -> f64 {
+        path.segments().map(|s| s.arclen(ACCURACY)).sum()
+    }
+
+    #[test]
+    fn full_range_keeps_path_closed() {
+        let path = trim_path(&SQUARE, 0.0, 1.0, 0.3);
+
+        assert_eq!(path.elements().last(), Some(&PathEl::ClosePath));
+        assert!((length(&path) - 40.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn part_of_path() {
+        let path = trim_path(&SQUARE, 0.0, 0.5, 0.0);
+
+        assert!((length(&path) - 20.0).abs() < 1e-6);
+        assert_eq!(path.elements().first(), Some(&PathEl::MoveTo(Point::ZERO)));
+        assert_eq!(
+            path.segments().last().unwrap().end(),
+            Point::new(10.0, 10.0)
+        );
+        assert!(!path.elements().contains(&PathEl::ClosePath));
+    }
+
+    #[test]
+    fn offset_wraps_around() {
+        let path = trim_path(&SQUARE, 0.0, 0.5, 0.875);
+
+        assert!((length(&path) - 20.0).abs() < 1e-6);
+        assert_eq!(
+            path.segments().next().unwrap().start(),
+            Point::new(0.0, 5.0)
+        );
+        assert_eq!(path.segments().last().unwrap().end(), Point::new(10.0, 5.0));
+    }
+
+    #[test]
+    fn empty_range() {
+        assert!(trim_path(&SQUARE, 0.6, 0.4, 0.0).elements().is_empty());
+        assert!(trim_path(&Rect::ZERO, 0.0, 0.5, 0.0).elements().is_empty());
+    }
+}
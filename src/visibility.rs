@@ -0,0 +1,278 @@
+//! Hiding entities, and skipping the ones that can't be seen.
+
+use crate::hierarchy::{propagate_hierarchy, Children, HierarchyRoot};
+use crate::prelude::*;
+use kurbo::{Join, Rect, Shape, Stroke, Vec2};
+
+/// Whether an entity is drawn or not.
+///
+/// - `Inherited` makes the entity visible only if its [`Parent`](crate::hierarchy::Parent) is (or if it has no parent).
+/// - `Visible` and `Hidden` ignore the parent.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Visibility {
+    #[default]
+    Inherited,
+    Visible,
+    Hidden,
+}
+
+/// The final visibility of an entity, after taking into account the ones of all of its parents. Calculated every frame by [`propagate_visibility`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InheritedVisibility(pub bool);
+
+/// The axis-aligned bounds of an entity's shape, relative to its [`Transform`]. Calculated by [`compute_bounds`] whenever the shape changes.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Aabb(pub Rect);
+
+/// How many entities were drawn, hidden or culled by the built-in drawing on the last frame.
+///
+/// - `hidden` counts the entities with an [`InheritedVisibility`] of `false`.
+/// - `culled` counts the entities whose [`Aabb`] was outside of every view.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct CullingStats {
+    pub drawn: usize,
+    pub hidden: usize,
+    pub culled: usize,
+}
+
+impl CullingStats {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Calculates the [`InheritedVisibility`] of every entity with a [`Transform`] or [`Children`], going from the entities without a [`Parent`](crate::hierarchy::Parent) down to their children.
+pub fn propagate_visibility(
+    mut commands: Commands,
+    roots: Query<Entity, HierarchyRoot<Transform>>,
+    nodes: Query<(Option<&Visibility>, Option<&Children>)>,
+    mut inherited: Query<&mut InheritedVisibility>,
+) {
+    propagate_hierarchy(&roots, &nodes, true, |entity, visibility, parent| {
+        let visible = resolve_visibility(visibility, parent);
+        set_inherited(&mut commands, &mut inherited, entity, visible);
+        visible
+    });
+}
+
+fn resolve_visibility(visibility: Option<&Visibility>, parent: bool) -> bool {
+    match visibility.copied().unwrap_or_default() {
+        Visibility::Inherited => parent,
+        Visibility::Visible => true,
+        Visibility::Hidden => false,
+    }
+}
+
+fn set_inherited(
+    commands: &mut Commands,
+    inherited: &mut Query<&mut InheritedVisibility>,
+    entity: Entity,
+    visible: bool,
+) {
+    match inherited.get_mut(entity) {
+        Ok(mut i) => i.0 = visible,
+        Err(_) => {
+            commands.entity(entity).insert(InheritedVisibility(visible));
+        }
+    }
+}
+
+/// Calculates the [`Aabb`] of every entity whose shape, text, stroke or shadow changed or was removed. Entities left without a shape lose their [`Aabb`].
+///
+/// Texts are shaped here too, into a [`TextLabelLayout`].
+#[allow(clippy::type_complexity)]
+pub fn compute_bounds(
+    mut commands: Commands,
+    query: Query<(
+        Option<&ShapeCircle>,
+        Option<&ShapeRoundedRect>,
+        Option<&ShapePath>,
+        Option<Ref<TextLabel>>,
+        Option<&TextLabelLayout>,
+        Option<&TextOutline>,
+        Option<&VectorSprite>,
+        Option<&StrokeStyle>,
+        Option<&Shadow>,
+        Option<&Anchor>,
+        Option<&Aabb>,
+    )>,
+    changed: Query<
+        Entity,
+        Or<(
+            Changed<ShapeCircle>,
+            Changed<ShapeRoundedRect>,
+            Changed<ShapePath>,
+            Changed<TextLabel>,
+            Changed<TextOutline>,
+            Changed<VectorSprite>,
+            Changed<StrokeStyle>,
+            Changed<Shadow>,
+            Changed<Anchor>,
+        )>,
+    >,
+    mut removed: (
+        RemovedComponents<ShapeCircle>,
+        RemovedComponents<ShapeRoundedRect>,
+        RemovedComponents<ShapePath>,
+        RemovedComponents<TextLabel>,
+        RemovedComponents<TextOutline>,
+        RemovedComponents<VectorSprite>,
+        RemovedComponents<StrokeStyle>,
+        RemovedComponents<Shadow>,
+        RemovedComponents<Anchor>,
+    ),
+) {
+    let mut entities: Vec<Entity> = changed.iter().collect();
+    entities.extend(removed.0.read());
+    entities.extend(removed.1.read());
+    entities.extend(removed.2.read());
+    entities.extend(removed.3.read());
+    entities.extend(removed.4.read());
+    entities.extend(removed.5.read());
+    entities.extend(removed.6.read());
+    entities.extend(removed.7.read());
+    entities.extend(removed.8.read());
+    entities.sort_unstable();
+    entities.dedup();
+
+    for entity in entities {
+        // Despawned entities are also reported as removed.
+        let Ok((
+            circle,
+            rounded_rect,
+            path,
+            text,
+            cached_layout,
+            outline,
+            sprite,
+            stroke,
+            shadow,
+            anchor,
+            aabb,
+        )) = query.get(entity)
+        else {
+            continue;
+        };
+
+        let mut bounds: Option<Rect> = None;
+        // Adds a rect once anchored, grown by `extent`.
+        let mut add = |r: Rect, extent: f64| {
+            let r = anchor
+                .map_or(r, |a| r + a.offset(r))
+                .inflate(extent, extent);
+            bounds = Some(bounds.map_or(r, |b| b.union(r)));
+        };
+
+        if let Some(c) = circle {
+            add(Rect::new(-c.radius, -c.radius, c.radius, c.radius), 0.0);
+        }
+
+        if let Some(r) = rounded_rect {
+            add(
+                Rect::new(
+                    -(r.size.x / 2.0),
+                    -(r.size.y / 2.0),
+                    r.size.x / 2.0,
+                    r.size.y / 2.0,
+                ),
+                0.0,
+            );
+        }
+
+        if let Some(p) = path {
+            add(p.0.bounding_box(), 0.0);
+        }
+
+        if let Some(l) = &text {
+            // The text is only shaped again when it changes.
+            let shaped;
+            let layout = match cached_layout {
+                Some(c) if !l.is_changed() => &c.0,
+                _ => {
+                    shaped = l.layout();
+                    commands
+                        .entity(entity)
+                        .insert(TextLabelLayout(shaped.clone()));
+                    &shaped
+                }
+            };
+            let extent = outline.map_or(0.0, |o| stroke_extent(&o.stroke));
+
+            // Without an anchor, texts are drawn from their top-left corner.
+            match anchor {
+                Some(_) => add(layout.bounds, extent),
+                None => add(layout.bounds + Vec2::new(0.0, layout.size()), extent),
+            }
+        }
+
+        if let Some(b) = sprite.and_then(|s| s.bounds()) {
+            add(b, 0.0);
+        }
+
+        if text.is_none() && cached_layout.is_some() {
+            commands.entity(entity).remove::<TextLabelLayout>();
+        }
+
+        let Some(mut bounds) = bounds else {
+            if aabb.is_some() {
+                commands.entity(entity).remove::<Aabb>();
+            }
+            continue;
+        };
+
+        if let Some(s) = stroke {
+            let extent = stroke_extent(&s.stroke);
+            bounds = bounds.inflate(extent, extent);
+        }
+
+        if let Some(s) = shadow {
+            bounds = bounds.inflate(s.extent(), s.extent());
+        }
+
+        if aabb.map(|a| a.0) != Some(bounds) {
+            commands.entity(entity).insert(Aabb(bounds));
+        }
+    }
+}
+
+/// How far a stroke reaches outside of its path. Miter joins can reach further than half the width, up to the miter limit.
+fn stroke_extent(stroke: &Stroke) -> f64 {
+    match stroke.join {
+        Join::Miter => stroke.miter_limit.max(1.0) * stroke.width / 2.0,
+        _ => stroke.width / 2.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hierarchy::Parent;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn hidden_root_without_transform() {
+        let mut world = World::new();
+        let root = world.spawn(Visibility::Hidden).id();
+        let middle = world.spawn((Parent(root), Transform::default())).id();
+        let child = world
+            .spawn((Parent(middle), Transform::default(), Visibility::Visible))
+            .id();
+        world.entity_mut(root).insert(Children(vec![middle]));
+        world.entity_mut(middle).insert(Children(vec![child]));
+
+        world.run_system_once(propagate_visibility);
+
+        assert_eq!(
+            world.get::<InheritedVisibility>(root),
+            Some(&InheritedVisibility(false))
+        );
+        assert_eq!(
+            world.get::<InheritedVisibility>(middle),
+            Some(&InheritedVisibility(false))
+        );
+        assert_eq!(
+            world.get::<InheritedVisibility>(child),
+            Some(&InheritedVisibility(true))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Opening, closing and assigning the windows of your [`App`].
+
+use crate::prelude::*;
+
+use crossbeam_queue::SegQueue;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A request sent by [`Windows`] that gets applied by the [`App`] at the end of the frame.
+pub(crate) enum WindowCommand {
+    Open {
+        id: usize,
+        title: String,
+        width: u32,
+        height: u32,
+        worlds: Vec<usize>,
+    },
+    Close(usize),
+    Assign {
+        world: usize,
+        window: usize,
+    },
+}
+
+/// The Resource that lets your systems manage the windows of your [`App`].
+///
+/// Every world has its own [`Windows`], but all of them share the same window ids, so a window opened from one world can be closed from another.
+/// Worlds are referred by the order they were created with [`App::new_world`], starting from `0`.
+#[derive(Resource, Default)]
+pub struct Windows {
+    current: Option<usize>,
+    next_id: Arc<AtomicUsize>,
+    command_queue: SegQueue<WindowCommand>,
+}
+
+impl Windows {
+    pub(crate) fn new(current: Option<usize>, next_id: Arc<AtomicUsize>) -> Self {
+        Self {
+            current,
+            next_id,
+            command_queue: SegQueue::new(),
+        }
+    }
+
+    /// The id of the window this world is being drawn into, `None` if its window was closed.
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub(crate) fn set_current(&mut self, id: Option<usize>) {
+        self.current = id;
+    }
+
+    /// Opens a new window and moves the given worlds into it. Returns the id of the new window.
+    pub fn open(&self, title: &str, width: u32, height: u32, worlds: &[usize]) -> usize {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        self.command_queue.push(WindowCommand::Open {
+            id,
+            title: title.to_string(),
+            width,
+            height,
+            worlds: worlds.to_vec(),
+        });
+
+        id
+    }
+
+    /// Closes a window. Closing the first window of the [`App`] exits the program.
+    ///
+    /// The worlds of the window are paused, until they're moved into another window with [`Windows::assign`] or [`Windows::open`].
+    pub fn close(&self, id: usize) {
+        self.command_queue.push(WindowCommand::Close(id));
+    }
+
+    /// Moves a world into another window.
+    pub fn assign(&self, world: usize, window: usize) {
+        self.command_queue
+            .push(WindowCommand::Assign { world, window });
+    }
+
+    pub(crate) fn pop_command(&self) -> Option<WindowCommand> {
+        self.command_queue.pop()
+    }
+}